@@ -0,0 +1,4 @@
+// Recompila quando as migrações mudam, já que elas são embutidas no binário
+fn main() {
+    println!("cargo:rerun-if-changed=migrations");
+}
@@ -0,0 +1,31 @@
+// Gera versões .br e .gz dos arquivos do build, servidas pelo backend conforme o
+// Accept-Encoding do navegador. Executado automaticamente depois de `npm run build`.
+const fs = require('fs');
+const path = require('path');
+const zlib = require('zlib');
+
+const BUILD_DIR = path.join(__dirname, '..', 'build');
+const EXTENSIONS = ['.html', '.js', '.css', '.json', '.svg', '.txt', '.map', '.ico'];
+// Arquivos menores que isso não ganham com a compressão
+const MIN_SIZE = 1024;
+
+function walk(dir) {
+    return fs.readdirSync(dir, { withFileTypes: true }).flatMap((entry) => {
+        const file = path.join(dir, entry.name);
+        return entry.isDirectory() ? walk(file) : [file];
+    });
+}
+
+for (const file of walk(BUILD_DIR)) {
+    if (!EXTENSIONS.includes(path.extname(file))) continue;
+    const data = fs.readFileSync(file);
+    if (data.length < MIN_SIZE) continue;
+
+    const brotli = zlib.brotliCompressSync(data, {
+        params: { [zlib.constants.BROTLI_PARAM_QUALITY]: zlib.constants.BROTLI_MAX_QUALITY },
+    });
+    const gzip = zlib.gzipSync(data, { level: zlib.constants.Z_BEST_COMPRESSION });
+    // Só vale a pena guardar a versão comprimida se ela for menor que o original
+    if (brotli.length < data.length) fs.writeFileSync(`${file}.br`, brotli);
+    if (gzip.length < data.length) fs.writeFileSync(`${file}.gz`, gzip);
+}
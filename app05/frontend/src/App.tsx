@@ -4,35 +4,46 @@ import { Box, Container } from '@mui/material';
 import TaskList from './components/TaskList';
 import TaskForm from './components/TaskForm';
 import Header from './components/Header';
+import Login from './components/Login';
 import { TaskProvider } from './contexts/TaskContext';
+import { AuthProvider, useAuthContext } from './contexts/AuthContext';
+
+// Redireciona para o login quando não há usuário autenticado
+const RequireAuth: React.FC<{ children: React.ReactElement }> = ({ children }) => {
+    const { isAuthenticated } = useAuthContext();
+    return isAuthenticated ? children : <Navigate to="/login" replace />;
+};
 
 const App: React.FC = () => {
     return (
-        <TaskProvider>
-            <Box sx={{ display: 'flex', flexDirection: 'column', minHeight: '100vh' }}>
-                <Header />
-                <Container component="main" sx={{ flexGrow: 1, py: 3 }}>
-                    <Routes>
-                        <Route path="/" element={<Navigate to="/tasks" replace />} />
-                        <Route path="/tasks" element={<TaskList />} />
-                        <Route path="/tasks/new" element={<TaskForm />} />
-                        <Route path="/tasks/edit/:id" element={<TaskForm />} />
-                    </Routes>
-                </Container>
-                <Box
-                    component="footer"
-                    sx={{
-                        py: 2,
-                        px: 2,
-                        mt: 'auto',
-                        backgroundColor: (theme) => theme.palette.grey[200],
-                        textAlign: 'center',
-                    }}
-                >
-                    Gerenciador de Tarefas com React e Rust © {new Date().getFullYear()}
+        <AuthProvider>
+            <TaskProvider>
+                <Box sx={{ display: 'flex', flexDirection: 'column', minHeight: '100vh' }}>
+                    <Header />
+                    <Container component="main" sx={{ flexGrow: 1, py: 3 }}>
+                        <Routes>
+                            <Route path="/" element={<Navigate to="/tasks" replace />} />
+                            <Route path="/login" element={<Login />} />
+                            <Route path="/tasks" element={<RequireAuth><TaskList /></RequireAuth>} />
+                            <Route path="/tasks/new" element={<RequireAuth><TaskForm /></RequireAuth>} />
+                            <Route path="/tasks/edit/:id" element={<RequireAuth><TaskForm /></RequireAuth>} />
+                        </Routes>
+                    </Container>
+                    <Box
+                        component="footer"
+                        sx={{
+                            py: 2,
+                            px: 2,
+                            mt: 'auto',
+                            backgroundColor: (theme) => theme.palette.grey[200],
+                            textAlign: 'center',
+                        }}
+                    >
+                        Gerenciador de Tarefas com React e Rust © {new Date().getFullYear()}
+                    </Box>
                 </Box>
-            </Box>
-        </TaskProvider>
+            </TaskProvider>
+        </AuthProvider>
     );
 };
 
@@ -13,10 +13,13 @@ import {
 import AddIcon from '@mui/icons-material/Add';
 import HomeIcon from '@mui/icons-material/Home';
 import ListAltIcon from '@mui/icons-material/ListAlt';
+import LogoutIcon from '@mui/icons-material/Logout';
+import { useAuthContext } from '../contexts/AuthContext';
 
 const Header: React.FC = () => {
     const theme = useTheme();
     const location = useLocation();
+    const { isAuthenticated, logout } = useAuthContext();
     const isTasksPage = location.pathname === '/tasks';
 
     return (
@@ -72,6 +75,12 @@ const Header: React.FC = () => {
                                 Nova Tarefa
                             </Button>
                         )}
+
+                        {isAuthenticated && (
+                            <IconButton color="inherit" onClick={logout} sx={{ ml: 1 }} title="Sair">
+                                <LogoutIcon />
+                            </IconButton>
+                        )}
                     </Box>
                 </Toolbar>
             </Container>
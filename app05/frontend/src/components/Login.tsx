@@ -0,0 +1,84 @@
+import React, { useState } from 'react';
+import { useNavigate } from 'react-router-dom';
+import axios from 'axios';
+import { Alert, Box, Button, Card, CardContent, CircularProgress, TextField, Typography } from '@mui/material';
+import { useAuthContext } from '../contexts/AuthContext';
+
+const Login: React.FC = () => {
+    const navigate = useNavigate();
+    const { login, register } = useAuthContext();
+    const [isRegister, setIsRegister] = useState(false);
+    const [username, setUsername] = useState('');
+    const [password, setPassword] = useState('');
+    const [loading, setLoading] = useState(false);
+    const [error, setError] = useState<string | null>(null);
+
+    // Enviar formulário de login ou cadastro
+    const handleSubmit = async (e: React.FormEvent) => {
+        e.preventDefault();
+        setError(null);
+        setLoading(true);
+        try {
+            if (isRegister) {
+                await register({ username, password });
+            } else {
+                await login({ username, password });
+            }
+            navigate('/tasks');
+        } catch (err) {
+            if (axios.isAxiosError(err) && err.response?.data?.message) {
+                setError(err.response.data.message);
+            } else {
+                setError('Erro ao processar sua solicitação. Tente novamente mais tarde.');
+            }
+        } finally {
+            setLoading(false);
+        }
+    };
+
+    return (
+        <Box sx={{ display: 'flex', justifyContent: 'center', mt: 4 }}>
+            <Card sx={{ width: '100%', maxWidth: 400 }}>
+                <CardContent>
+                    <Typography variant="h5" gutterBottom>
+                        {isRegister ? 'Criar Conta' : 'Entrar'}
+                    </Typography>
+                    {error && (
+                        <Alert severity="error" sx={{ mb: 2 }}>
+                            {error}
+                        </Alert>
+                    )}
+                    <Box component="form" onSubmit={handleSubmit} noValidate>
+                        <TextField
+                            required
+                            fullWidth
+                            margin="normal"
+                            label="Usuário"
+                            value={username}
+                            onChange={(e) => setUsername(e.target.value)}
+                            disabled={loading}
+                        />
+                        <TextField
+                            required
+                            fullWidth
+                            margin="normal"
+                            type="password"
+                            label="Senha"
+                            value={password}
+                            onChange={(e) => setPassword(e.target.value)}
+                            disabled={loading}
+                        />
+                        <Button type="submit" fullWidth variant="contained" sx={{ mt: 2 }} disabled={loading}>
+                            {loading ? <CircularProgress size={24} /> : isRegister ? 'Cadastrar' : 'Entrar'}
+                        </Button>
+                        <Button fullWidth sx={{ mt: 1 }} onClick={() => setIsRegister(!isRegister)} disabled={loading}>
+                            {isRegister ? 'Já tenho uma conta' : 'Criar uma conta'}
+                        </Button>
+                    </Box>
+                </CardContent>
+            </Card>
+        </Box>
+    );
+};
+
+export default Login;
@@ -17,7 +17,7 @@ import {
     Typography,
 } from '@mui/material';
 import { useTaskContext } from '../contexts/TaskContext';
-import { getTask } from '../services/api';
+import { getTask, ValidationError } from '../services/api';
 import {
     TaskFormData,
     TaskPriority,
@@ -29,8 +29,8 @@ import {
 const initialFormState: TaskFormData = {
     title: '',
     description: '',
-    status: TaskStatus.TODO,
-    priority: TaskPriority.MEDIUM,
+    status: TaskStatus.Pendente,
+    priority: TaskPriority.Media,
 };
 
 // Estado inicial dos erros do formulário
@@ -46,6 +46,7 @@ const TaskForm: React.FC = () => {
     const navigate = useNavigate();
     const { addTask, editTask } = useTaskContext();
     const [formData, setFormData] = useState<TaskFormData>(initialFormState);
+    const [version, setVersion] = useState<number | undefined>(undefined);
     const [errors, setErrors] = useState(initialErrorState);
     const [loading, setLoading] = useState(false);
     const [fetchingTask, setFetchingTask] = useState(false);
@@ -69,6 +70,7 @@ const TaskForm: React.FC = () => {
                             status: taskData.status,
                             priority: taskData.priority,
                         });
+                        setVersion(taskData.version);
                     } else {
                         setError('Tarefa não encontrada');
                         setTimeout(() => {
@@ -137,6 +139,25 @@ const TaskForm: React.FC = () => {
         return valid;
     };
 
+    // Exibir os erros de validação do servidor junto aos campos; os que não têm
+    // campo no formulário aparecem no alerta
+    const showValidationErrors = (err: ValidationError) => {
+        const newErrors = { ...initialErrorState };
+        const others: string[] = [];
+        for (const { field, message } of err.fields) {
+            if (field in newErrors) {
+                const key = field as keyof typeof newErrors;
+                newErrors[key] = newErrors[key] || message;
+            } else {
+                others.push(message);
+            }
+        }
+        setErrors(newErrors);
+        if (others.length > 0 || err.fields.length === 0) {
+            setError(others.length > 0 ? others.join('; ') : err.message);
+        }
+    };
+
     // Enviar formulário
     const handleSubmit = async (e: React.FormEvent) => {
         e.preventDefault();
@@ -150,7 +171,7 @@ const TaskForm: React.FC = () => {
         setLoading(true);
         try {
             if (isEditMode && id) {
-                const result = await editTask(id, formData);
+                const result = await editTask(id, formData, version);
                 if (result) {
                     setSuccessMessage('Tarefa atualizada com sucesso');
                     setTimeout(() => {
@@ -171,7 +192,11 @@ const TaskForm: React.FC = () => {
                 }
             }
         } catch (err) {
-            setError('Erro ao processar sua solicitação. Tente novamente mais tarde.');
+            if (err instanceof ValidationError) {
+                showValidationErrors(err);
+            } else {
+                setError('Erro ao processar sua solicitação. Tente novamente mais tarde.');
+            }
         } finally {
             setLoading(false);
         }
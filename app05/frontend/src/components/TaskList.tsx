@@ -195,6 +195,15 @@ const TaskList: React.FC = () => {
                                                         color: 'white',
                                                     }}
                                                 />
+                                                {task.labels?.map((label) => (
+                                                    <Chip
+                                                        key={label.id}
+                                                        label={label.name}
+                                                        size="small"
+                                                        variant="outlined"
+                                                        sx={{ borderColor: label.color, color: label.color }}
+                                                    />
+                                                ))}
                                             </Box>
                                             <Divider sx={{ mb: 1 }} />
                                             <Box sx={{ display: 'flex', flexWrap: 'wrap', gap: 2 }}>
@@ -0,0 +1,60 @@
+import React, { createContext, useState, useContext, useCallback } from 'react';
+import { Credentials } from '../types/User';
+import { getToken, setToken, login as loginRequest, register as registerRequest } from '../services/api';
+
+interface AuthContextData {
+    token: string | null;
+    isAuthenticated: boolean;
+    login: (credentials: Credentials) => Promise<void>;
+    register: (credentials: Credentials) => Promise<void>;
+    logout: () => void;
+}
+
+const AuthContext = createContext<AuthContextData>({} as AuthContextData);
+
+export const AuthProvider: React.FC<{ children: React.ReactNode }> = ({ children }) => {
+    const [token, setTokenState] = useState<string | null>(getToken());
+
+    const saveToken = useCallback((value: string | null) => {
+        setToken(value);
+        setTokenState(value);
+    }, []);
+
+    const login = useCallback(async (credentials: Credentials) => {
+        const result = await loginRequest(credentials);
+        saveToken(result.token);
+    }, [saveToken]);
+
+    const register = useCallback(async (credentials: Credentials) => {
+        const result = await registerRequest(credentials);
+        saveToken(result.token);
+    }, [saveToken]);
+
+    const logout = useCallback(() => {
+        saveToken(null);
+    }, [saveToken]);
+
+    return (
+        <AuthContext.Provider
+            value={{
+                token,
+                isAuthenticated: Boolean(token),
+                login,
+                register,
+                logout,
+            }}
+        >
+            {children}
+        </AuthContext.Provider>
+    );
+};
+
+export const useAuthContext = (): AuthContextData => {
+    const context = useContext(AuthContext);
+    if (!context) {
+        throw new Error('useAuthContext must be used within an AuthProvider');
+    }
+    return context;
+};
+
+export default AuthContext;
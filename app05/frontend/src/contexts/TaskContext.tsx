@@ -1,30 +1,36 @@
 import React, { createContext, useState, useEffect, useContext, useCallback } from 'react';
 import { Task, TaskFormData, TaskUpdateData } from '../types/Task';
-import { getTasks, createTask, updateTask, deleteTask } from '../services/api';
+import { getTasks, createTask, updateTask, deleteTask, ValidationError } from '../services/api';
+import { useAuthContext } from './AuthContext';
 
 interface TaskContextData {
     tasks: Task[];
     loading: boolean;
     error: string | null;
+    hasMore: boolean;
     fetchTasks: () => Promise<void>;
+    loadMoreTasks: () => Promise<void>;
     addTask: (taskData: TaskFormData) => Promise<Task | null>;
-    editTask: (id: string, taskData: TaskUpdateData) => Promise<Task | null>;
+    editTask: (id: string, taskData: TaskUpdateData, version?: number) => Promise<Task | null>;
     removeTask: (id: string) => Promise<boolean>;
 }
 
 const TaskContext = createContext<TaskContextData>({} as TaskContextData);
 
 export const TaskProvider: React.FC<{ children: React.ReactNode }> = ({ children }) => {
+    const { token } = useAuthContext();
     const [tasks, setTasks] = useState<Task[]>([]);
     const [loading, setLoading] = useState(true);
     const [error, setError] = useState<string | null>(null);
+    const [nextCursor, setNextCursor] = useState<string | undefined>(undefined);
 
     const fetchTasks = useCallback(async () => {
         try {
             setLoading(true);
             setError(null);
-            const data = await getTasks();
-            setTasks(data);
+            const page = await getTasks();
+            setTasks(page.tasks);
+            setNextCursor(page.next_cursor);
         } catch (err) {
             setError('Falha ao carregar tarefas. Tente novamente mais tarde.');
             console.error('Erro ao buscar tarefas:', err);
@@ -33,6 +39,21 @@ export const TaskProvider: React.FC<{ children: React.ReactNode }> = ({ children
         }
     }, []);
 
+    // Carrega a próxima página usando o cursor retornado pela API
+    const loadMoreTasks = useCallback(async () => {
+        if (!nextCursor) {
+            return;
+        }
+        try {
+            const page = await getTasks({ cursor: nextCursor });
+            setTasks((prevTasks) => [...prevTasks, ...page.tasks]);
+            setNextCursor(page.next_cursor);
+        } catch (err) {
+            setError('Falha ao carregar mais tarefas. Tente novamente.');
+            console.error('Erro ao buscar mais tarefas:', err);
+        }
+    }, [nextCursor]);
+
     const addTask = useCallback(async (taskData: TaskFormData): Promise<Task | null> => {
         try {
             const newTask = await createTask(taskData);
@@ -42,23 +63,31 @@ export const TaskProvider: React.FC<{ children: React.ReactNode }> = ({ children
             }
             return null;
         } catch (err) {
+            // Erros de validação são exibidos pelo formulário, junto aos campos
+            if (err instanceof ValidationError) {
+                throw err;
+            }
             setError('Falha ao criar tarefa. Tente novamente.');
             console.error('Erro ao adicionar tarefa:', err);
             return null;
         }
     }, []);
 
-    const editTask = useCallback(async (id: string, taskData: TaskUpdateData): Promise<Task | null> => {
+    const editTask = useCallback(async (id: string, taskData: TaskUpdateData, version?: number): Promise<Task | null> => {
         try {
-            const updatedTask = await updateTask(id, taskData);
+            const updatedTask = await updateTask(id, taskData, version);
             if (updatedTask) {
                 setTasks((prevTasks) =>
-                    prevTasks.map((task) => (task.id === id ? updatedTask : task))
+                    // Preserva os campos calculados (etiquetas, progresso) que a gravação não devolve
+                    prevTasks.map((task) => (task.id === id ? { ...task, ...updatedTask } : task))
                 );
                 return updatedTask;
             }
             return null;
         } catch (err) {
+            if (err instanceof ValidationError) {
+                throw err;
+            }
             setError('Falha ao atualizar tarefa. Tente novamente.');
             console.error('Erro ao editar tarefa:', err);
             return null;
@@ -81,8 +110,53 @@ export const TaskProvider: React.FC<{ children: React.ReactNode }> = ({ children
     }, []);
 
     useEffect(() => {
-        fetchTasks();
-    }, [fetchTasks]);
+        if (token) {
+            fetchTasks();
+        } else {
+            setTasks([]);
+        }
+    }, [fetchTasks, token]);
+
+    // Recebe alterações feitas por outros usuários em tempo real.
+    // O EventSource reconecta sozinho e envia o Last-Event-ID para recuperar eventos perdidos.
+    // O EventSource não envia cabeçalhos, então o token vai na URL.
+    useEffect(() => {
+        if (!token) {
+            return;
+        }
+        const source = new EventSource(`/tasks/events?access_token=${encodeURIComponent(token)}`);
+
+        const upsert = (event: MessageEvent) => {
+            const task: Task = JSON.parse(event.data);
+            setTasks((prevTasks) =>
+                prevTasks.some((t) => t.id === task.id)
+                    ? prevTasks.map((t) => (t.id === task.id ? { ...t, ...task } : t))
+                    : [task, ...prevTasks]
+            );
+        };
+
+        const remove = (event: MessageEvent) => {
+            const task: Task = JSON.parse(event.data);
+            setTasks((prevTasks) => prevTasks.filter((t) => t.id !== task.id));
+        };
+
+        // Lembretes disparados pelo agendador do servidor
+        const remind = (event: MessageEvent) => {
+            const task: Task = JSON.parse(event.data);
+            if ('Notification' in window && Notification.permission === 'granted') {
+                new Notification(`Lembrete: ${task.title}`);
+            } else {
+                console.info(`Lembrete: ${task.title}`);
+            }
+        };
+
+        source.addEventListener('created', upsert);
+        source.addEventListener('updated', upsert);
+        source.addEventListener('deleted', remove);
+        source.addEventListener('reminder', remind);
+
+        return () => source.close();
+    }, [token]);
 
     return (
         <TaskContext.Provider
@@ -90,7 +164,9 @@ export const TaskProvider: React.FC<{ children: React.ReactNode }> = ({ children
                 tasks,
                 loading,
                 error,
+                hasMore: Boolean(nextCursor),
                 fetchTasks,
+                loadMoreTasks,
                 addTask,
                 editTask,
                 removeTask,
@@ -1,5 +1,27 @@
 import axios from 'axios';
-import { Task, TaskFormData, TaskUpdateData, ApiResponse } from '../types/Task';
+import { Task, TaskFormData, TaskUpdateData, TaskMoveData, TaskPage, TaskQueryParams, SearchHit, ApiResponse, FieldError } from '../types/Task';
+import { Board, Project, ProjectDetails } from '../types/Project';
+import { AuthResponse, Credentials } from '../types/User';
+import { Comment, NewComment } from '../types/Comment';
+import { Attachment } from '../types/Attachment';
+import { AuditPage } from '../types/Activity';
+import { Dependency, TaskGraph } from '../types/Dependency';
+import { Label, LabelInput } from '../types/Label';
+import { BatchMode, BatchOperation, BatchSummary } from '../types/Batch';
+import { SyncChange, SyncPage, SyncResult } from '../types/Sync';
+import { StatsQueryParams, TaskStats } from '../types/Stats';
+
+const TOKEN_KEY = 'token';
+
+export const getToken = (): string | null => localStorage.getItem(TOKEN_KEY);
+
+export const setToken = (token: string | null) => {
+    if (token) {
+        localStorage.setItem(TOKEN_KEY, token);
+    } else {
+        localStorage.removeItem(TOKEN_KEY);
+    }
+};
 
 const api = axios.create({
     baseURL: '/',
@@ -8,15 +30,66 @@ const api = axios.create({
     },
 });
 
-export const getTasks = async (): Promise<Task[]> => {
+// Envia o token de acesso em todas as requisições
+api.interceptors.request.use((config) => {
+    const token = getToken();
+    if (token) {
+        config.headers.Authorization = `Bearer ${token}`;
+    }
+    return config;
+});
+
+export const login = async (credentials: Credentials): Promise<AuthResponse> => {
+    const response = await api.post<ApiResponse<AuthResponse>>('/auth/login', credentials);
+    if (!response.data.success || !response.data.data) {
+        throw new Error(response.data.message);
+    }
+    return response.data.data;
+};
+
+export const register = async (credentials: Credentials): Promise<AuthResponse> => {
+    const response = await api.post<ApiResponse<AuthResponse>>('/auth/register', credentials);
+    if (!response.data.success || !response.data.data) {
+        throw new Error(response.data.message);
+    }
+    return response.data.data;
+};
+
+export const getTasks = async (params: TaskQueryParams = {}): Promise<TaskPage> => {
     try {
-        const response = await api.get<ApiResponse<Task[]>>('/tasks');
+        const response = await api.get<ApiResponse<TaskPage>>('/tasks', { params });
         if (response.data.success && response.data.data) {
             return response.data.data;
         }
-        return [];
+        return { tasks: [] };
     } catch (error) {
         console.error('Erro ao buscar tarefas:', error);
+        return { tasks: [] };
+    }
+};
+
+export const getOverdueTasks = async (): Promise<Task[]> => {
+    try {
+        const response = await api.get<ApiResponse<Task[]>>('/tasks/overdue');
+        if (response.data.success && response.data.data) {
+            return response.data.data;
+        }
+        return [];
+    } catch (error) {
+        console.error('Erro ao buscar tarefas atrasadas:', error);
+        return [];
+    }
+};
+
+export const getUpcomingTasks = async (days = 7): Promise<Task[]> => {
+    try {
+        const response = await api.get<ApiResponse<Task[]>>('/tasks/upcoming', { params: { days } });
+        if (response.data.success && response.data.data) {
+            return response.data.data;
+        }
+        return [];
+    } catch (error) {
+        console.error('Erro ao buscar próximas tarefas:', error);
         return [];
     }
 };
@@ -34,40 +107,386 @@ export const getTask = async (id: string): Promise<Task | null> => {
     }
 };
 
-export const createTask = async (taskData: TaskFormData): Promise<Task | null> => {
+// Dados recusados pela validação do servidor (422), com os campos e os motivos
+export class ValidationError extends Error {
+    fields: FieldError[];
+
+    constructor(message: string, fields: FieldError[]) {
+        super(message);
+        this.fields = fields;
+    }
+}
+
+const validationError = (error: unknown): ValidationError | null => {
+    if (axios.isAxiosError(error) && error.response?.status === 422) {
+        const { message, errors } = error.response.data ?? {};
+        return new ValidationError(message ?? 'Dados inválidos', errors ?? []);
+    }
+    return null;
+};
+
+const CREATE_ATTEMPTS = 3;
+
+// Falhas de rede são tentadas de novo com a mesma Idempotency-Key, então a tarefa
+// nunca é criada duas vezes
+export const createTask = async (taskData: TaskFormData, idempotencyKey: string = crypto.randomUUID()): Promise<Task | null> => {
+    const headers = { 'Idempotency-Key': idempotencyKey };
+    for (let attempt = 1; ; attempt++) {
+        try {
+            const response = await api.post<ApiResponse<Task>>('/tasks', taskData, { headers });
+            if (response.data.success && response.data.data) {
+                return response.data.data;
+            }
+            return null;
+        } catch (error) {
+            if (attempt < CREATE_ATTEMPTS && axios.isAxiosError(error) && !error.response) {
+                continue;
+            }
+            const invalid = validationError(error);
+            if (invalid) {
+                throw invalid;
+            }
+            console.error('Erro ao criar tarefa:', error);
+            return null;
+        }
+    }
+};
+
+// Com `version`, envia If-Match para não sobrescrever alterações feitas por outra pessoa
+export const updateTask = async (id: string, taskData: TaskUpdateData, version?: number): Promise<Task | null> => {
     try {
-        const response = await api.post<ApiResponse<Task>>('/tasks', taskData);
+        const headers = version !== undefined ? { 'If-Match': `"${version}"` } : undefined;
+        const response = await api.put<ApiResponse<Task>>(`/tasks/${id}`, taskData, { headers });
         if (response.data.success && response.data.data) {
             return response.data.data;
         }
         return null;
     } catch (error) {
-        console.error('Erro ao criar tarefa:', error);
+        const invalid = validationError(error);
+        if (invalid) {
+            throw invalid;
+        }
+        console.error(`Erro ao atualizar tarefa ${id}:`, error);
         return null;
     }
 };
 
-export const updateTask = async (id: string, taskData: TaskUpdateData): Promise<Task | null> => {
+export const deleteTask = async (id: string): Promise<boolean> => {
     try {
-        const response = await api.put<ApiResponse<Task>>(`/tasks/${id}`, taskData);
+        const response = await api.delete<ApiResponse<null>>(`/tasks/${id}`);
+        return response.data.success;
+    } catch (error) {
+        console.error(`Erro ao excluir tarefa ${id}:`, error);
+        return false;
+    }
+};
+
+// Em all_or_nothing, um lote desfeito responde com erro, mas ainda traz o resultado de cada operação
+export const getTaskStats = async (params: StatsQueryParams = {}): Promise<TaskStats | null> => {
+    try {
+        const response = await api.get<ApiResponse<TaskStats>>('/tasks/stats', { params });
         if (response.data.success && response.data.data) {
             return response.data.data;
         }
         return null;
     } catch (error) {
-        console.error(`Erro ao atualizar tarefa ${id}:`, error);
+        console.error('Erro ao buscar estatísticas:', error);
         return null;
     }
 };
 
-export const deleteTask = async (id: string): Promise<boolean> => {
+export const runBatch = async (operations: BatchOperation[], mode: BatchMode = 'all_or_nothing'): Promise<BatchSummary | null> => {
     try {
-        const response = await api.delete<ApiResponse<null>>(`/tasks/${id}`);
+        const response = await api.post<ApiResponse<BatchSummary>>('/tasks/batch', { mode, operations });
+        return response.data.data ?? null;
+    } catch (error) {
+        console.error('Erro ao executar lote de tarefas:', error);
+        if (axios.isAxiosError(error) && error.response?.data?.data) {
+            return error.response.data.data as BatchSummary;
+        }
+        return null;
+    }
+};
+
+export const moveTask = async (id: string, moveData: TaskMoveData): Promise<Task | null> => {
+    try {
+        const response = await api.post<ApiResponse<Task>>(`/tasks/${id}/move`, moveData);
+        if (response.data.success && response.data.data) {
+            return response.data.data;
+        }
+        return null;
+    } catch (error) {
+        console.error(`Erro ao mover tarefa ${id}:`, error);
+        return null;
+    }
+};
+
+export const getProjects = async (): Promise<Project[]> => {
+    try {
+        const response = await api.get<ApiResponse<Project[]>>('/projects');
+        if (response.data.success && response.data.data) {
+            return response.data.data;
+        }
+        return [];
+    } catch (error) {
+        console.error('Erro ao buscar projetos:', error);
+        return [];
+    }
+};
+
+export const createProject = async (name: string): Promise<ProjectDetails | null> => {
+    try {
+        const response = await api.post<ApiResponse<ProjectDetails>>('/projects', { name });
+        if (response.data.success && response.data.data) {
+            return response.data.data;
+        }
+        return null;
+    } catch (error) {
+        console.error('Erro ao criar projeto:', error);
+        return null;
+    }
+};
+
+export const getBoard = async (projectId: string): Promise<Board | null> => {
+    try {
+        const response = await api.get<ApiResponse<Board>>(`/projects/${projectId}/board`);
+        if (response.data.success && response.data.data) {
+            return response.data.data;
+        }
+        return null;
+    } catch (error) {
+        console.error(`Erro ao buscar quadro ${projectId}:`, error);
+        return null;
+    }
+};
+
+export const getComments = async (taskId: string): Promise<Comment[]> => {
+    try {
+        const response = await api.get<ApiResponse<Comment[]>>(`/tasks/${taskId}/comments`);
+        if (response.data.success && response.data.data) {
+            return response.data.data;
+        }
+        return [];
+    } catch (error) {
+        console.error(`Erro ao buscar comentários da tarefa ${taskId}:`, error);
+        return [];
+    }
+};
+
+export const createComment = async (taskId: string, comment: NewComment): Promise<Comment | null> => {
+    try {
+        const response = await api.post<ApiResponse<Comment>>(`/tasks/${taskId}/comments`, comment);
+        if (response.data.success && response.data.data) {
+            return response.data.data;
+        }
+        return null;
+    } catch (error) {
+        console.error('Erro ao criar comentário:', error);
+        return null;
+    }
+};
+
+export const getAttachments = async (taskId: string): Promise<Attachment[]> => {
+    try {
+        const response = await api.get<ApiResponse<Attachment[]>>(`/tasks/${taskId}/attachments`);
+        if (response.data.success && response.data.data) {
+            return response.data.data;
+        }
+        return [];
+    } catch (error) {
+        console.error(`Erro ao buscar anexos da tarefa ${taskId}:`, error);
+        return [];
+    }
+};
+
+export const uploadAttachments = async (taskId: string, files: File[]): Promise<Attachment[]> => {
+    try {
+        const form = new FormData();
+        files.forEach(file => form.append('file', file));
+        const response = await api.post<ApiResponse<Attachment[]>>(`/tasks/${taskId}/attachments`, form, {
+            headers: { 'Content-Type': 'multipart/form-data' },
+        });
+        if (response.data.success && response.data.data) {
+            return response.data.data;
+        }
+        return [];
+    } catch (error) {
+        console.error('Erro ao enviar anexos:', error);
+        return [];
+    }
+};
+
+export const getTaskHistory = async (taskId: string, cursor?: string): Promise<AuditPage> => {
+    try {
+        const response = await api.get<ApiResponse<AuditPage>>(`/tasks/${taskId}/history`, { params: { cursor } });
+        if (response.data.success && response.data.data) {
+            return response.data.data;
+        }
+        return { events: [] };
+    } catch (error) {
+        console.error(`Erro ao buscar histórico da tarefa ${taskId}:`, error);
+        return { events: [] };
+    }
+};
+
+export const getActivity = async (cursor?: string): Promise<AuditPage> => {
+    try {
+        const response = await api.get<ApiResponse<AuditPage>>('/activity', { params: { cursor } });
+        if (response.data.success && response.data.data) {
+            return response.data.data;
+        }
+        return { events: [] };
+    } catch (error) {
+        console.error('Erro ao buscar atividades:', error);
+        return { events: [] };
+    }
+};
+
+export const addDependency = async (taskId: string, blockerId: string): Promise<Dependency | null> => {
+    try {
+        const response = await api.post<ApiResponse<Dependency>>(`/tasks/${taskId}/dependencies`, { blocker_id: blockerId });
+        if (response.data.success && response.data.data) {
+            return response.data.data;
+        }
+        return null;
+    } catch (error) {
+        console.error('Erro ao criar dependência:', error);
+        return null;
+    }
+};
+
+export const removeDependency = async (taskId: string, blockerId: string): Promise<boolean> => {
+    try {
+        const response = await api.delete<ApiResponse<null>>(`/tasks/${taskId}/dependencies/${blockerId}`);
         return response.data.success;
     } catch (error) {
-        console.error(`Erro ao excluir tarefa ${id}:`, error);
+        console.error('Erro ao remover dependência:', error);
+        return false;
+    }
+};
+
+export const getTaskGraph = async (taskId: string): Promise<TaskGraph | null> => {
+    try {
+        const response = await api.get<ApiResponse<TaskGraph>>(`/tasks/${taskId}/graph`);
+        if (response.data.success && response.data.data) {
+            return response.data.data;
+        }
+        return null;
+    } catch (error) {
+        console.error(`Erro ao buscar grafo da tarefa ${taskId}:`, error);
+        return null;
+    }
+};
+
+export const getLabels = async (): Promise<Label[]> => {
+    try {
+        const response = await api.get<ApiResponse<Label[]>>('/labels');
+        if (response.data.success && response.data.data) {
+            return response.data.data;
+        }
+        return [];
+    } catch (error) {
+        console.error('Erro ao buscar etiquetas:', error);
+        return [];
+    }
+};
+
+export const createLabel = async (label: LabelInput): Promise<Label | null> => {
+    try {
+        const response = await api.post<ApiResponse<Label>>('/labels', label);
+        if (response.data.success && response.data.data) {
+            return response.data.data;
+        }
+        return null;
+    } catch (error) {
+        console.error('Erro ao criar etiqueta:', error);
+        return null;
+    }
+};
+
+export const updateLabel = async (id: string, label: LabelInput): Promise<Label | null> => {
+    try {
+        const response = await api.put<ApiResponse<Label>>(`/labels/${id}`, label);
+        if (response.data.success && response.data.data) {
+            return response.data.data;
+        }
+        return null;
+    } catch (error) {
+        console.error(`Erro ao atualizar etiqueta ${id}:`, error);
+        return null;
+    }
+};
+
+export const deleteLabel = async (id: string): Promise<boolean> => {
+    try {
+        const response = await api.delete<ApiResponse<null>>(`/labels/${id}`);
+        return response.data.success;
+    } catch (error) {
+        console.error(`Erro ao excluir etiqueta ${id}:`, error);
         return false;
     }
 };
 
-export default api; 
\ No newline at end of file
+export const attachLabel = async (taskId: string, labelId: string): Promise<Task | null> => {
+    try {
+        const response = await api.put<ApiResponse<Task>>(`/tasks/${taskId}/labels/${labelId}`);
+        if (response.data.success && response.data.data) {
+            return response.data.data;
+        }
+        return null;
+    } catch (error) {
+        console.error('Erro ao aplicar etiqueta:', error);
+        return null;
+    }
+};
+
+export const detachLabel = async (taskId: string, labelId: string): Promise<Task | null> => {
+    try {
+        const response = await api.delete<ApiResponse<Task>>(`/tasks/${taskId}/labels/${labelId}`);
+        if (response.data.success && response.data.data) {
+            return response.data.data;
+        }
+        return null;
+    } catch (error) {
+        console.error('Erro ao remover etiqueta:', error);
+        return null;
+    }
+};
+
+export const searchTasks = async (q: string, limit?: number): Promise<SearchHit[]> => {
+    try {
+        const response = await api.get<ApiResponse<SearchHit[]>>('/tasks/search', { params: { q, limit } });
+        if (response.data.success && response.data.data) {
+            return response.data.data;
+        }
+        return [];
+    } catch (error) {
+        console.error('Erro ao buscar tarefas:', error);
+        return [];
+    }
+};
+
+export default api; 
+
+export const pullChanges = async (since?: string, limit?: number): Promise<SyncPage | null> => {
+    try {
+        const response = await api.get<ApiResponse<SyncPage>>('/sync', { params: { since, limit } });
+        if (response.data.success && response.data.data) {
+            return response.data.data;
+        }
+        return null;
+    } catch (error) {
+        console.error('Erro ao baixar alterações:', error);
+        return null;
+    }
+};
+
+export const pushChanges = async (changes: SyncChange[]): Promise<SyncResult[] | null> => {
+    try {
+        const response = await api.post<ApiResponse<SyncResult[]>>('/sync', { changes });
+        return response.data.data ?? null;
+    } catch (error) {
+        console.error('Erro ao enviar alterações:', error);
+        return null;
+    }
+};
@@ -0,0 +1,19 @@
+export interface FieldChange {
+    old: unknown;
+    new: unknown;
+}
+
+export interface AuditEntry {
+    id: number;
+    task_id: string;
+    actor_id: string;
+    actor_name: string | null;
+    action: 'created' | 'updated' | 'deleted';
+    changes: Record<string, FieldChange>;
+    created_at: string;
+}
+
+export interface AuditPage {
+    events: AuditEntry[];
+    next_cursor?: string;
+}
@@ -0,0 +1,10 @@
+export interface Attachment {
+    id: string;
+    task_id: string;
+    filename: string;
+    content_type: string;
+    size: number;
+    sha256: string;
+    uploaded_by: string;
+    created_at: string;
+}
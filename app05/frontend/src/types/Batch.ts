@@ -0,0 +1,26 @@
+import { Task, TaskFormData, TaskUpdateData } from './Task';
+import { TaskNode } from './Dependency';
+
+export type BatchMode = 'all_or_nothing' | 'best_effort';
+
+export type BatchOperation =
+    | { op: 'create'; task: TaskFormData }
+    | { op: 'update'; id: string; changes: TaskUpdateData; version?: number }
+    | { op: 'delete'; id: string; version?: number };
+
+export interface BatchItemResult {
+    index: number;
+    op: BatchOperation['op'];
+    success: boolean;
+    status: number;
+    message: string;
+    data?: Task;
+    blockers?: TaskNode[];
+}
+
+export interface BatchSummary {
+    mode: BatchMode;
+    succeeded: number;
+    failed: number;
+    results: BatchItemResult[];
+}
@@ -0,0 +1,23 @@
+export interface Comment {
+    id: string;
+    task_id: string;
+    author_id: string;
+    author_name: string;
+    parent_id: string | null;
+    body: string;
+    created_at: string;
+    updated_at: string;
+    deleted_at: string | null;
+    edited: boolean;
+    replies: Comment[];
+}
+
+export interface CommentRevision {
+    body: string;
+    edited_at: string;
+}
+
+export interface NewComment {
+    body: string;
+    parent_id?: string;
+}
@@ -0,0 +1,22 @@
+import { TaskStatus } from './Task';
+
+export interface TaskNode {
+    id: string;
+    title: string;
+    status: TaskStatus;
+    priority: number;
+    due_at: string | null;
+}
+
+export interface Dependency {
+    blocker_id: string;
+    blocked_id: string;
+    created_at: string;
+}
+
+export interface TaskGraph {
+    task: TaskNode;
+    upstream: TaskNode[];
+    downstream: TaskNode[];
+    edges: Dependency[];
+}
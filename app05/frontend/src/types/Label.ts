@@ -0,0 +1,16 @@
+export interface Label {
+    id: string;
+    owner_id: string;
+    name: string;
+    color: string;
+    created_at: string;
+    updated_at: string;
+}
+
+// Etiqueta como aparece dentro da tarefa, gerada a partir do modelo em Rust
+export type { TaskLabel } from './generated';
+
+export interface LabelInput {
+    name: string;
+    color?: string;
+}
@@ -0,0 +1,27 @@
+import { Task, TaskStatus } from './Task';
+
+export interface Project {
+    id: string;
+    name: string;
+    owner_id: string;
+    created_at: string;
+    updated_at: string;
+}
+
+export interface ProjectColumn {
+    status: TaskStatus;
+    name: string;
+    position: number;
+}
+
+export interface ProjectDetails extends Project {
+    columns: ProjectColumn[];
+}
+
+export interface BoardColumn extends ProjectColumn {
+    tasks: Task[];
+}
+
+export interface Board extends Project {
+    columns: BoardColumn[];
+}
@@ -0,0 +1,44 @@
+import { TaskStatus } from './Task';
+
+export type StatsBucket = 'day' | 'week';
+
+export interface StatusCount {
+    status: TaskStatus;
+    count: number;
+}
+
+export interface PriorityCount {
+    priority: number;
+    count: number;
+}
+
+export interface TimelinePoint {
+    // Início do período (YYYY-MM-DD, em UTC)
+    period: string;
+    created: number;
+    completed: number;
+}
+
+export interface TaskStats {
+    total: number;
+    by_status: StatusCount[];
+    by_priority: PriorityCount[];
+    overdue: {
+        total: number;
+        by_priority: PriorityCount[];
+    };
+    from: string;
+    to: string;
+    bucket: StatsBucket;
+    timeline: TimelinePoint[];
+    lead_time: {
+        completed: number;
+        average_hours: number | null;
+    };
+}
+
+export interface StatsQueryParams {
+    from?: string;
+    to?: string;
+    bucket?: StatsBucket;
+}
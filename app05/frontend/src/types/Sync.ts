@@ -0,0 +1,32 @@
+import { Task, TaskFormData, TaskUpdateData } from './Task';
+
+export interface Tombstone {
+    id: string;
+    deleted_at: string;
+}
+
+export interface SyncPage {
+    tasks: Task[];
+    tombstones: Tombstone[];
+    cursor: string;
+    has_more: boolean;
+}
+
+export type SyncChange =
+    | { op: 'create'; task: TaskFormData & { id?: string } }
+    | { op: 'update'; id: string; base_version?: number; base_updated_at?: string; changes: TaskUpdateData }
+    | { op: 'delete'; id: string; base_version?: number; base_updated_at?: string };
+
+export type SyncOutcome = 'applied' | 'conflict' | 'rejected';
+
+export interface SyncResult {
+    index: number;
+    op: SyncChange['op'];
+    id: string;
+    outcome: SyncOutcome;
+    status: number;
+    message: string;
+    // Cópia atual da tarefa no servidor
+    task?: Task;
+    deleted?: boolean;
+}
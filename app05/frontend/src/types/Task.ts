@@ -1,54 +1,68 @@
-export interface Task {
-    id: string;
-    title: string;
-    description: string;
-    status: string;
-    priority: number;
-    created_at: string;
-    updated_at: string;
-}
+// Task, TaskUpdate, ApiResponse, FieldError e os enums de status e prioridade são gerados a
+// partir dos modelos em Rust (src/typescript.rs)
+import { Task, TaskPriority, TaskStatus, TaskUpdate } from './generated';
+
+export { TaskPriority, TaskStatus } from './generated';
+export type { ApiResponse, FieldError, Task, TaskUpdate } from './generated';
 
-export type TaskFormData = Omit<Task, 'id' | 'created_at' | 'updated_at'>;
+export type TaskFormData = Omit<Task, 'id' | 'created_at' | 'updated_at' | 'owner_id' | 'version' | 'position' | 'series_id' | 'occurrence' | 'progress' | 'labels'>;
 
-export type TaskUpdateData = Partial<TaskFormData>;
+export type TaskUpdateData = TaskUpdate;
+
+export interface SearchHit extends Task {
+    score: number;
+    // Trechos em HTML escapado, com os termos encontrados entre <mark> e </mark>
+    title_highlight: string;
+    snippet: string;
+}
 
-export interface ApiResponse<T> {
-    success: boolean;
-    message: string;
-    data?: T;
+export interface TaskPage {
+    tasks: Task[];
+    next_cursor?: string;
 }
 
-export enum TaskStatus {
-    TODO = 'Pendente',
-    IN_PROGRESS = 'Em Andamento',
-    DONE = 'Concluída',
-    CANCELLED = 'Cancelada',
+export interface TaskQueryParams {
+    status?: string;
+    priority_min?: number;
+    priority_max?: number;
+    created_from?: string;
+    created_to?: string;
+    updated_from?: string;
+    updated_to?: string;
+    q?: string;
+    parent_id?: string;
+    // Nomes separados por vírgula; `labels_match=all` exige todas
+    labels?: string;
+    labels_match?: 'any' | 'all';
+    sort?: 'created_at' | 'updated_at' | 'priority' | 'title';
+    order?: 'asc' | 'desc';
+    limit?: number;
+    cursor?: string;
 }
 
-export enum TaskPriority {
-    LOW = 1,
-    MEDIUM = 2,
-    HIGH = 3,
-    URGENT = 4,
+export interface TaskMoveData {
+    status: TaskStatus;
+    after_id?: string;
+    project_id?: string;
 }
 
 export const TaskPriorityLabels: Record<TaskPriority, string> = {
-    [TaskPriority.LOW]: 'Baixa',
-    [TaskPriority.MEDIUM]: 'Média',
-    [TaskPriority.HIGH]: 'Alta',
-    [TaskPriority.URGENT]: 'Urgente',
+    [TaskPriority.Baixa]: 'Baixa',
+    [TaskPriority.Media]: 'Média',
+    [TaskPriority.Alta]: 'Alta',
+    [TaskPriority.Urgente]: 'Urgente',
 };
 
 export const TaskPriorityColors: Record<TaskPriority, string> = {
-    [TaskPriority.LOW]: '#58b09c',
-    [TaskPriority.MEDIUM]: '#f9c74f',
-    [TaskPriority.HIGH]: '#f8961e',
-    [TaskPriority.URGENT]: '#f25c54',
+    [TaskPriority.Baixa]: '#58b09c',
+    [TaskPriority.Media]: '#f9c74f',
+    [TaskPriority.Alta]: '#f8961e',
+    [TaskPriority.Urgente]: '#f25c54',
 };
 
 export const TaskStatusColors: Record<string, string> = {
-    [TaskStatus.TODO]: '#1976d2',
-    [TaskStatus.IN_PROGRESS]: '#9c27b0',
-    [TaskStatus.DONE]: '#388e3c',
-    [TaskStatus.CANCELLED]: '#757575',
+    [TaskStatus.Pendente]: '#1976d2',
+    [TaskStatus['Em Andamento']]: '#9c27b0',
+    [TaskStatus.Concluída]: '#388e3c',
+    [TaskStatus.Cancelada]: '#757575',
 }; 
\ No newline at end of file
@@ -0,0 +1,15 @@
+export interface User {
+    id: string;
+    username: string;
+    created_at: string;
+}
+
+export interface Credentials {
+    username: string;
+    password: string;
+}
+
+export interface AuthResponse {
+    token: string;
+    user: User;
+}
@@ -0,0 +1,67 @@
+// Gerado a partir dos modelos em Rust (src/typescript.rs). Não edite à mão; para atualizar:
+//
+//     UPDATE_TS_TYPES=1 cargo test typescript
+
+export enum TaskStatus {
+    "Pendente" = "Pendente",
+    "Em Andamento" = "Em Andamento",
+    "Concluída" = "Concluída",
+    "Cancelada" = "Cancelada",
+}
+
+export enum TaskPriority {
+    "Baixa" = 1,
+    "Media" = 2,
+    "Alta" = 3,
+    "Urgente" = 4,
+}
+
+export type TaskLabel = {
+    id: string;
+    name: string;
+    color: string;
+};
+
+export type Task = {
+    id: string;
+    title: string;
+    description: string;
+    status: TaskStatus;
+    priority: TaskPriority;
+    created_at: string;
+    updated_at: string;
+    owner_id: string;
+    version: number;
+    project_id?: string | null;
+    position: number;
+    due_at?: string | null;
+    remind_at?: string | null;
+    recurrence?: string | null;
+    series_id?: string | null;
+    occurrence: number;
+    parent_id?: string | null;
+    progress?: number;
+    labels?: Array<TaskLabel>;
+};
+
+export type TaskUpdate = {
+    title?: string;
+    description?: string;
+    status?: TaskStatus;
+    priority?: TaskPriority;
+    due_at?: string | null;
+    remind_at?: string | null;
+    recurrence?: string | null;
+    parent_id?: string | null;
+};
+
+export type ApiResponse<T> = {
+    success: boolean;
+    message: string;
+    data?: T;
+};
+
+export type FieldError = {
+    field: string;
+    message: string;
+};
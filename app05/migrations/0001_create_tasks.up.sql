@@ -0,0 +1,44 @@
+-- Esquema inicial da tabela de tarefas (equivalente ao antigo init_database)
+CREATE TABLE IF NOT EXISTS tasks (
+    id TEXT PRIMARY KEY,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    status TEXT NOT NULL,
+    priority INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+-- Bancos antigos podem ter status livres, que o enum TaskStatus não consegue ler.
+-- Os valores conhecidos viram o status equivalente; os demais viram Pendente.
+UPDATE tasks
+SET status = CASE
+    WHEN lower(trim(status)) IN ('pendente', 'a fazer', 'todo', 'to do', 'to_do')
+        THEN 'Pendente'
+    WHEN lower(trim(status)) IN ('em andamento', 'em_andamento', 'andamento', 'fazendo',
+                                 'in progress', 'in_progress', 'doing')
+        THEN 'Em Andamento'
+    WHEN lower(trim(status)) IN ('concluída', 'concluida', 'concluído',
+                                 'concluido', 'feito', 'finalizada', 'done', 'completed')
+        THEN 'Concluída'
+    WHEN lower(trim(status)) IN ('cancelada', 'cancelado', 'cancelled', 'canceled')
+        THEN 'Cancelada'
+    ELSE 'Pendente'
+END
+WHERE status NOT IN ('Pendente', 'Em Andamento', 'Concluída', 'Cancelada');
+
+-- O SQLite só aceita CHECK na criação da tabela; os triggers fazem o papel da restrição
+-- para bancos que já existiam
+CREATE TRIGGER IF NOT EXISTS tasks_status_check_insert
+BEFORE INSERT ON tasks
+WHEN new.status NOT IN ('Pendente', 'Em Andamento', 'Concluída', 'Cancelada')
+BEGIN
+    SELECT RAISE(ABORT, 'Status inválido');
+END;
+
+CREATE TRIGGER IF NOT EXISTS tasks_status_check_update
+BEFORE UPDATE OF status ON tasks
+WHEN new.status NOT IN ('Pendente', 'Em Andamento', 'Concluída', 'Cancelada')
+BEGIN
+    SELECT RAISE(ABORT, 'Status inválido');
+END;
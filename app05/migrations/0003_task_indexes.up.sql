@@ -0,0 +1,4 @@
+-- Índices usados pelos filtros e ordenações de GET /tasks
+CREATE INDEX IF NOT EXISTS idx_tasks_status ON tasks (status);
+CREATE INDEX IF NOT EXISTS idx_tasks_priority ON tasks (priority);
+CREATE INDEX IF NOT EXISTS idx_tasks_created_at ON tasks (created_at);
@@ -0,0 +1,2 @@
+-- Versão da tarefa para controle de concorrência otimista (ETag / If-Match)
+ALTER TABLE tasks ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
@@ -0,0 +1,8 @@
+DROP INDEX IF EXISTS idx_tasks_project_position;
+
+ALTER TABLE tasks DROP COLUMN position;
+ALTER TABLE tasks DROP COLUMN project_id;
+
+DROP TABLE IF EXISTS project_columns;
+DROP INDEX IF EXISTS idx_projects_owner_id;
+DROP TABLE IF EXISTS projects;
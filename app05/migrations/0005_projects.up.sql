@@ -0,0 +1,25 @@
+-- Projetos (quadros) com colunas ordenadas mapeadas para status
+CREATE TABLE IF NOT EXISTS projects (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    owner_id TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_projects_owner_id ON projects (owner_id);
+
+CREATE TABLE IF NOT EXISTS project_columns (
+    project_id TEXT NOT NULL REFERENCES projects (id) ON DELETE CASCADE,
+    status TEXT NOT NULL,
+    name TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    PRIMARY KEY (project_id, status)
+);
+
+-- Cada tarefa pode pertencer a um projeto e tem uma posição fracionária dentro da coluna.
+-- As tarefas de um projeto excluído são removidas pela aplicação.
+ALTER TABLE tasks ADD COLUMN project_id TEXT;
+ALTER TABLE tasks ADD COLUMN position REAL NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_tasks_project_position ON tasks (project_id, status, position);
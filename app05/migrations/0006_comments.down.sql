@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_comment_revisions_comment_id;
+DROP TABLE IF EXISTS comment_revisions;
+DROP INDEX IF EXISTS idx_comments_task_id;
+DROP TABLE IF EXISTS comments;
@@ -0,0 +1,22 @@
+-- Comentários das tarefas, com respostas encadeadas e histórico de edições
+CREATE TABLE IF NOT EXISTS comments (
+    id TEXT PRIMARY KEY,
+    task_id TEXT NOT NULL REFERENCES tasks (id) ON DELETE CASCADE,
+    author_id TEXT NOT NULL REFERENCES users (id),
+    parent_id TEXT REFERENCES comments (id) ON DELETE CASCADE,
+    body TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    deleted_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_comments_task_id ON comments (task_id, created_at);
+
+CREATE TABLE IF NOT EXISTS comment_revisions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    comment_id TEXT NOT NULL REFERENCES comments (id) ON DELETE CASCADE,
+    body TEXT NOT NULL,
+    edited_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_comment_revisions_comment_id ON comment_revisions (comment_id);
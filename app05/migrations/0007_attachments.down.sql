@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_attachments_sha256;
+DROP INDEX IF EXISTS idx_attachments_task_id;
+DROP TABLE IF EXISTS attachments;
+DROP TABLE IF EXISTS attachment_blobs;
@@ -0,0 +1,21 @@
+-- Conteúdo dos anexos, endereçado pelo hash SHA-256 e compartilhado entre anexos iguais
+CREATE TABLE IF NOT EXISTS attachment_blobs (
+    sha256 TEXT PRIMARY KEY,
+    size INTEGER NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+-- Arquivos anexados às tarefas
+CREATE TABLE IF NOT EXISTS attachments (
+    id TEXT PRIMARY KEY,
+    task_id TEXT NOT NULL REFERENCES tasks (id) ON DELETE CASCADE,
+    sha256 TEXT NOT NULL REFERENCES attachment_blobs (sha256),
+    filename TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    size INTEGER NOT NULL,
+    uploaded_by TEXT NOT NULL REFERENCES users (id),
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_attachments_task_id ON attachments (task_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_attachments_sha256 ON attachments (sha256);
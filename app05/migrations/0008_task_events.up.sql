@@ -0,0 +1,26 @@
+-- Registro somente de inclusão de todas as alterações de tarefas.
+-- Não há chave estrangeira para tasks: o histórico sobrevive à exclusão da tarefa.
+CREATE TABLE IF NOT EXISTS task_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    task_id TEXT NOT NULL,
+    owner_id TEXT NOT NULL,
+    actor_id TEXT NOT NULL,
+    action TEXT NOT NULL,
+    changes TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_events_task_id ON task_events (task_id, id);
+CREATE INDEX IF NOT EXISTS idx_task_events_owner_id ON task_events (owner_id, id);
+
+CREATE TRIGGER IF NOT EXISTS task_events_no_update
+BEFORE UPDATE ON task_events
+BEGIN
+    SELECT RAISE(ABORT, 'task_events é somente de inclusão');
+END;
+
+CREATE TRIGGER IF NOT EXISTS task_events_no_delete
+BEFORE DELETE ON task_events
+BEGIN
+    SELECT RAISE(ABORT, 'task_events é somente de inclusão');
+END;
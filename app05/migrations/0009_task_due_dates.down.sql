@@ -0,0 +1,5 @@
+DROP INDEX IF EXISTS idx_tasks_pending_reminders;
+DROP INDEX IF EXISTS idx_tasks_due_at;
+ALTER TABLE tasks DROP COLUMN reminded_at;
+ALTER TABLE tasks DROP COLUMN remind_at;
+ALTER TABLE tasks DROP COLUMN due_at;
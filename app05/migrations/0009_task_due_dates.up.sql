@@ -0,0 +1,8 @@
+-- Prazo e lembrete opcionais das tarefas.
+-- reminded_at marca o lembrete já disparado para que não se repita após reiniciar.
+ALTER TABLE tasks ADD COLUMN due_at TEXT;
+ALTER TABLE tasks ADD COLUMN remind_at TEXT;
+ALTER TABLE tasks ADD COLUMN reminded_at TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_tasks_due_at ON tasks (due_at);
+CREATE INDEX IF NOT EXISTS idx_tasks_pending_reminders ON tasks (remind_at) WHERE reminded_at IS NULL;
@@ -0,0 +1,7 @@
+-- Regra de repetição (RRULE) e a série à qual cada ocorrência pertence
+ALTER TABLE tasks ADD COLUMN recurrence TEXT;
+ALTER TABLE tasks ADD COLUMN series_id TEXT;
+ALTER TABLE tasks ADD COLUMN occurrence INTEGER NOT NULL DEFAULT 1;
+
+-- Cada ocorrência de uma série é criada uma única vez
+CREATE UNIQUE INDEX IF NOT EXISTS idx_tasks_series_occurrence ON tasks (series_id, occurrence);
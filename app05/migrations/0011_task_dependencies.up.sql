@@ -0,0 +1,10 @@
+-- Dependências entre tarefas: `blocker_id` precisa ser finalizada antes de `blocked_id`
+CREATE TABLE IF NOT EXISTS task_dependencies (
+    blocker_id TEXT NOT NULL REFERENCES tasks (id) ON DELETE CASCADE,
+    blocked_id TEXT NOT NULL REFERENCES tasks (id) ON DELETE CASCADE,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (blocker_id, blocked_id),
+    CHECK (blocker_id <> blocked_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_dependencies_blocked_id ON task_dependencies (blocked_id);
@@ -0,0 +1,6 @@
+-- Subtarefas: qualquer tarefa pode ter uma tarefa pai, com aninhamento livre.
+-- Sem ON DELETE CASCADE: a aplicação exclui a subárvore inteira na mesma instrução
+-- para registrar cada tarefa excluída no histórico.
+ALTER TABLE tasks ADD COLUMN parent_id TEXT REFERENCES tasks (id);
+
+CREATE INDEX IF NOT EXISTS idx_tasks_parent_id ON tasks (parent_id);
@@ -0,0 +1,74 @@
+-- Busca textual (FTS5) em título, descrição e comentários das tarefas.
+-- remove_diacritics faz "concluida" encontrar "Concluída".
+CREATE VIRTUAL TABLE IF NOT EXISTS tasks_fts USING fts5 (
+    title,
+    description,
+    comments,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+-- Liga cada tarefa a um rowid estável no índice; o rowid implícito de tasks pode
+-- mudar em um VACUUM, já que a chave primária é textual
+CREATE TABLE IF NOT EXISTS task_search_docs (
+    doc_id INTEGER PRIMARY KEY,
+    task_id TEXT NOT NULL UNIQUE
+);
+
+INSERT INTO task_search_docs (task_id) SELECT id FROM tasks;
+
+INSERT INTO tasks_fts (rowid, title, description, comments)
+SELECT d.doc_id, t.title, t.description,
+       COALESCE((SELECT group_concat(c.body, ' ') FROM comments c
+                 WHERE c.task_id = t.id AND c.deleted_at IS NULL), '')
+FROM tasks t
+JOIN task_search_docs d ON d.task_id = t.id;
+
+CREATE TRIGGER IF NOT EXISTS tasks_fts_insert
+AFTER INSERT ON tasks
+BEGIN
+    INSERT INTO task_search_docs (task_id) VALUES (new.id);
+    INSERT INTO tasks_fts (rowid, title, description, comments)
+    VALUES ((SELECT doc_id FROM task_search_docs WHERE task_id = new.id), new.title, new.description, '');
+END;
+
+CREATE TRIGGER IF NOT EXISTS tasks_fts_update
+AFTER UPDATE OF title, description ON tasks
+BEGIN
+    UPDATE tasks_fts SET title = new.title, description = new.description
+    WHERE rowid = (SELECT doc_id FROM task_search_docs WHERE task_id = new.id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS tasks_fts_delete
+AFTER DELETE ON tasks
+BEGIN
+    DELETE FROM tasks_fts WHERE rowid = (SELECT doc_id FROM task_search_docs WHERE task_id = old.id);
+    DELETE FROM task_search_docs WHERE task_id = old.id;
+END;
+
+-- Os comentários entram no índice concatenados; qualquer mudança reconstrói a coluna da tarefa
+CREATE TRIGGER IF NOT EXISTS comments_fts_insert
+AFTER INSERT ON comments
+BEGIN
+    UPDATE tasks_fts
+    SET comments = COALESCE((SELECT group_concat(c.body, ' ') FROM comments c
+                             WHERE c.task_id = new.task_id AND c.deleted_at IS NULL), '')
+    WHERE rowid = (SELECT doc_id FROM task_search_docs WHERE task_id = new.task_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS comments_fts_update
+AFTER UPDATE OF body, deleted_at ON comments
+BEGIN
+    UPDATE tasks_fts
+    SET comments = COALESCE((SELECT group_concat(c.body, ' ') FROM comments c
+                             WHERE c.task_id = new.task_id AND c.deleted_at IS NULL), '')
+    WHERE rowid = (SELECT doc_id FROM task_search_docs WHERE task_id = new.task_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS comments_fts_delete
+AFTER DELETE ON comments
+BEGIN
+    UPDATE tasks_fts
+    SET comments = COALESCE((SELECT group_concat(c.body, ' ') FROM comments c
+                             WHERE c.task_id = old.task_id AND c.deleted_at IS NULL), '')
+    WHERE rowid = (SELECT doc_id FROM task_search_docs WHERE task_id = old.task_id);
+END;
@@ -0,0 +1,15 @@
+-- Respostas de POST /tasks guardadas por chave de idempotência, para repetir a
+-- resposta original quando o cliente reenvia a mesma requisição
+CREATE TABLE IF NOT EXISTS idempotency_keys (
+    owner_id TEXT NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    key TEXT NOT NULL,
+    request_hash TEXT NOT NULL,
+    status INTEGER NOT NULL,
+    etag TEXT,
+    body TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL,
+    PRIMARY KEY (owner_id, key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_idempotency_keys_expires_at ON idempotency_keys (expires_at);
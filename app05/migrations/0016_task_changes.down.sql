@@ -0,0 +1,9 @@
+DROP TRIGGER IF EXISTS task_changes_label_update;
+DROP TRIGGER IF EXISTS task_changes_label_delete;
+DROP TRIGGER IF EXISTS task_changes_label_insert;
+DROP TRIGGER IF EXISTS task_changes_delete;
+DROP TRIGGER IF EXISTS task_changes_update;
+DROP TRIGGER IF EXISTS task_changes_insert;
+DROP INDEX IF EXISTS idx_task_changes_task;
+DROP INDEX IF EXISTS idx_task_changes_owner_seq;
+DROP TABLE IF EXISTS task_changes;
@@ -0,0 +1,82 @@
+-- Última alteração de cada tarefa, para a sincronização incremental (GET /sync).
+-- seq cresce a cada alteração e serve de cursor. Tarefas excluídas ficam aqui como
+-- tombstones, com deleted_at preenchido, para que os clientes saibam removê-las.
+-- Os registros são identificados pelo dono e pela tarefa: a criação de uma tarefa com o
+-- mesmo ID por outro usuário não pode apagar o tombstone do primeiro dono.
+CREATE TABLE IF NOT EXISTS task_changes (
+    seq INTEGER PRIMARY KEY AUTOINCREMENT,
+    task_id TEXT NOT NULL,
+    owner_id TEXT NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    deleted_at TEXT,
+    UNIQUE (owner_id, task_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_changes_owner_seq ON task_changes (owner_id, seq);
+CREATE INDEX IF NOT EXISTS idx_task_changes_task ON task_changes (task_id);
+
+-- Tarefas sem dono (de bancos anteriores às contas de usuário) ficam de fora até que a
+-- migração 0018 as passe para o usuário legado; a atualização então as registra aqui.
+INSERT INTO task_changes (task_id, owner_id)
+SELECT id, owner_id FROM tasks WHERE owner_id <> '' ORDER BY updated_at;
+
+-- Cada alteração move a tarefa para o fim da sequência. A tarefa pai também é marcada,
+-- porque o progresso dela depende das subtarefas. Só tarefas que ainda existem são
+-- marcadas, e só registros de tarefas vivas são removidos, para não apagar um tombstone.
+CREATE TRIGGER IF NOT EXISTS task_changes_insert
+AFTER INSERT ON tasks
+BEGIN
+    DELETE FROM task_changes
+    WHERE deleted_at IS NULL AND task_id IN (SELECT id FROM tasks WHERE id IN (new.id, new.parent_id));
+    INSERT INTO task_changes (task_id, owner_id)
+    SELECT id, owner_id FROM tasks WHERE id IN (new.id, new.parent_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS task_changes_update
+AFTER UPDATE ON tasks
+BEGIN
+    DELETE FROM task_changes
+    WHERE deleted_at IS NULL
+      AND task_id IN (SELECT id FROM tasks WHERE id IN (new.id, new.parent_id, old.parent_id));
+    INSERT INTO task_changes (task_id, owner_id)
+    SELECT id, owner_id FROM tasks
+    WHERE id IN (new.id, new.parent_id, old.parent_id) AND owner_id <> '';
+END;
+
+CREATE TRIGGER IF NOT EXISTS task_changes_delete
+AFTER DELETE ON tasks
+BEGIN
+    DELETE FROM task_changes
+    WHERE deleted_at IS NULL
+      AND (task_id = old.id OR task_id IN (SELECT id FROM tasks WHERE id = old.parent_id));
+    INSERT INTO task_changes (task_id, owner_id, deleted_at)
+    VALUES (old.id, old.owner_id, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'));
+    INSERT INTO task_changes (task_id, owner_id)
+    SELECT id, owner_id FROM tasks WHERE id = old.parent_id;
+END;
+
+-- As etiquetas fazem parte da tarefa sincronizada
+CREATE TRIGGER IF NOT EXISTS task_changes_label_insert
+AFTER INSERT ON task_labels
+BEGIN
+    DELETE FROM task_changes
+    WHERE deleted_at IS NULL AND task_id IN (SELECT id FROM tasks WHERE id = new.task_id);
+    INSERT INTO task_changes (task_id, owner_id) SELECT id, owner_id FROM tasks WHERE id = new.task_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS task_changes_label_delete
+AFTER DELETE ON task_labels
+BEGIN
+    DELETE FROM task_changes
+    WHERE deleted_at IS NULL AND task_id IN (SELECT id FROM tasks WHERE id = old.task_id);
+    INSERT INTO task_changes (task_id, owner_id) SELECT id, owner_id FROM tasks WHERE id = old.task_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS task_changes_label_update
+AFTER UPDATE OF name, color ON labels
+BEGIN
+    DELETE FROM task_changes
+    WHERE deleted_at IS NULL AND task_id IN (SELECT task_id FROM task_labels WHERE label_id = new.id);
+    INSERT INTO task_changes (task_id, owner_id)
+    SELECT t.id, t.owner_id FROM task_labels tl JOIN tasks t ON t.id = tl.task_id
+    WHERE tl.label_id = new.id;
+END;
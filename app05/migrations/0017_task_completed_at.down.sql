@@ -0,0 +1,4 @@
+DROP TRIGGER IF EXISTS tasks_completed_at_update;
+DROP TRIGGER IF EXISTS tasks_completed_at_insert;
+DROP INDEX IF EXISTS idx_tasks_completed_at;
+ALTER TABLE tasks DROP COLUMN completed_at;
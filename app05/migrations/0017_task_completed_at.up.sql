@@ -0,0 +1,25 @@
+-- Momento em que a tarefa foi concluída, usado nas estatísticas de tempo de entrega.
+-- Mantido por triggers em todos os caminhos de gravação; para as tarefas que já estavam
+-- concluídas, a última atualização serve de aproximação.
+ALTER TABLE tasks ADD COLUMN completed_at TEXT;
+
+UPDATE tasks SET completed_at = updated_at WHERE status = 'Concluída';
+
+CREATE INDEX IF NOT EXISTS idx_tasks_completed_at ON tasks (completed_at);
+
+CREATE TRIGGER IF NOT EXISTS tasks_completed_at_insert
+AFTER INSERT ON tasks
+WHEN new.status = 'Concluída'
+BEGIN
+    UPDATE tasks SET completed_at = new.updated_at WHERE id = new.id;
+END;
+
+-- Reabrir a tarefa limpa a data; concluir de novo grava a nova
+CREATE TRIGGER IF NOT EXISTS tasks_completed_at_update
+AFTER UPDATE OF status ON tasks
+WHEN new.status IS NOT old.status
+BEGIN
+    UPDATE tasks
+    SET completed_at = CASE WHEN new.status = 'Concluída' THEN new.updated_at END
+    WHERE id = new.id;
+END;
@@ -0,0 +1,3 @@
+-- As tarefas voltam a ficar sem dono. O usuário legado é mantido, porque pode ter
+-- projetos, etiquetas e comentários criados depois de receber uma senha.
+UPDATE tasks SET owner_id = '' WHERE owner_id = '00000000-0000-0000-0000-000000000000';
@@ -0,0 +1,8 @@
+-- Tarefas criadas antes das contas de usuário ficaram com owner_id vazio e não aparecem
+-- para ninguém. Elas passam para o usuário "legado", criado só quando essas tarefas
+-- existem. Ele nasce sem senha; veja LEGACY_USER_PASSWORD no README.
+INSERT OR IGNORE INTO users (id, username, password_hash, created_at)
+SELECT '00000000-0000-0000-0000-000000000000', 'legado', '', strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+WHERE EXISTS (SELECT 1 FROM tasks WHERE owner_id = '');
+
+UPDATE tasks SET owner_id = '00000000-0000-0000-0000-000000000000' WHERE owner_id = '';
@@ -0,0 +1,553 @@
+use actix_multipart::Multipart;
+use actix_web::{
+    delete, get,
+    http::header::{
+        self, Charset, ContentDisposition, DispositionParam, DispositionType, ExtendedValue,
+    },
+    post,
+    web::{self, BytesMut},
+    HttpResponse, Responder,
+};
+use chrono::{DateTime, Utc};
+use futures_util::StreamExt;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::{sqlite::SqlitePool, FromRow};
+use std::env;
+use tokio::sync::Mutex;
+use utoipa::{
+    openapi::{
+        schema::{ArrayBuilder, KnownFormat, ObjectBuilder, Schema, SchemaFormat, Type},
+        RefOr,
+    },
+    PartialSchema, ToSchema,
+};
+use uuid::Uuid;
+
+use crate::auth::AuthUser;
+use crate::error::AppError;
+use crate::openapi::Message;
+use crate::storage::{BlobStore, LocalStore};
+use crate::{check_task, ApiResponse};
+
+// Tamanho máximo padrão de cada arquivo (10 MiB)
+const DEFAULT_MAX_SIZE: usize = 10 * 1024 * 1024;
+// Tamanho máximo padrão de um envio, somando todas as partes do formulário (32 MiB)
+const DEFAULT_MAX_UPLOAD_SIZE: usize = 32 * 1024 * 1024;
+// Quantidade máxima de arquivos por envio
+const MAX_FILES_PER_UPLOAD: usize = 10;
+// Tipos de arquivo aceitos: capturas de tela, logs e documentos comuns
+const ALLOWED_TYPES: &[&str] = &[
+    "image/png",
+    "image/jpeg",
+    "image/gif",
+    "image/webp",
+    "text/plain",
+    "text/csv",
+    "application/json",
+    "application/pdf",
+    "application/zip",
+    "application/gzip",
+];
+
+// Modelo de anexo
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct Attachment {
+    pub id: String,
+    pub task_id: String,
+    pub filename: String,
+    pub content_type: String,
+    pub size: i64,
+    pub sha256: String,
+    pub uploaded_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+// Formulário de envio, descrito apenas na documentação da API: qualquer campo com nome
+// de arquivo é tratado como anexo. O corpo é lido parte a parte por `read_uploads`, por isso o
+// tipo só existe para o esquema e não tem campos.
+struct UploadForm;
+
+impl PartialSchema for UploadForm {
+    fn schema() -> RefOr<Schema> {
+        let file = ObjectBuilder::new()
+            .schema_type(Type::String)
+            .format(Some(SchemaFormat::KnownFormat(KnownFormat::Binary)));
+        ObjectBuilder::new()
+            .property("files", ArrayBuilder::new().items(file))
+            .required("files")
+            .into()
+    }
+}
+
+impl ToSchema for UploadForm {}
+
+// Arquivo recebido e validado, ainda não gravado
+struct Upload {
+    filename: String,
+    content_type: String,
+    sha256: String,
+    data: web::Bytes,
+}
+
+// Armazenamento dos anexos e limites de envio, lidos do arquivo .env
+pub struct Attachments {
+    store: Box<dyn BlobStore>,
+    max_size: usize,
+    max_upload_size: usize,
+    // Serializa gravações e limpezas para que um conteúdo recém-enviado
+    // não seja removido como órfão antes de ser registrado no banco
+    lock: Mutex<()>,
+}
+
+impl Attachments {
+    pub fn new(store: Box<dyn BlobStore>, max_size: usize, max_upload_size: usize) -> Self {
+        Attachments {
+            store,
+            max_size,
+            max_upload_size,
+            lock: Mutex::new(()),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        let dir = env::var("ATTACHMENTS_DIR").unwrap_or_else(|_| "attachments".to_string());
+        let max_size = env::var("ATTACHMENT_MAX_SIZE")
+            .map(|value| {
+                value
+                    .parse::<usize>()
+                    .expect("ATTACHMENT_MAX_SIZE deve ser um número de bytes")
+            })
+            .unwrap_or(DEFAULT_MAX_SIZE);
+        let max_upload_size = env::var("ATTACHMENT_MAX_UPLOAD_SIZE")
+            .map(|value| {
+                value
+                    .parse::<usize>()
+                    .expect("ATTACHMENT_MAX_UPLOAD_SIZE deve ser um número de bytes")
+            })
+            .unwrap_or(DEFAULT_MAX_UPLOAD_SIZE);
+
+        Attachments::new(Box::new(LocalStore::new(dir)), max_size, max_upload_size)
+    }
+
+    // Remove o conteúdo gravado por um envio que falhou, a menos que outro anexo já o use.
+    // Deve ser chamado com `lock` adquirido; falhas são apenas registradas.
+    async fn discard(&self, db: &SqlitePool, uploads: &[Upload]) {
+        for upload in uploads {
+            let referenced = sqlx::query_scalar::<_, bool>(
+                "SELECT EXISTS (SELECT 1 FROM attachment_blobs WHERE sha256 = ?)",
+            )
+            .bind(&upload.sha256)
+            .fetch_one(db)
+            .await;
+            match referenced {
+                Ok(true) => {}
+                Ok(false) => {
+                    if let Err(e) = self.store.delete(&upload.sha256).await {
+                        log::error!("Erro ao remover o arquivo {}: {}", upload.sha256, e);
+                    }
+                }
+                Err(e) => log::error!("Erro ao verificar o arquivo {}: {}", upload.sha256, e),
+            }
+        }
+    }
+
+    // Remove do armazenamento o conteúdo que nenhum anexo referencia mais.
+    // Chamado depois de excluir anexos ou tarefas; falhas são apenas registradas.
+    pub async fn cleanup(&self, db: &SqlitePool) {
+        let _guard = self.lock.lock().await;
+
+        let orphans = match sqlx::query_scalar::<_, String>(
+            r#"
+            DELETE FROM attachment_blobs
+            WHERE NOT EXISTS (SELECT 1 FROM attachments a WHERE a.sha256 = attachment_blobs.sha256)
+            RETURNING sha256
+            "#,
+        )
+        .fetch_all(db)
+        .await
+        {
+            Ok(orphans) => orphans,
+            Err(e) => {
+                log::error!("Erro ao limpar anexos órfãos: {}", e);
+                return;
+            }
+        };
+
+        for sha256 in orphans {
+            if let Err(e) = self.store.delete(&sha256).await {
+                log::error!("Erro ao remover o arquivo {}: {}", sha256, e);
+            }
+        }
+    }
+}
+
+fn attachment_not_found(id: &str) -> AppError {
+    AppError::NotFound(format!("Anexo com ID {} não encontrado", id))
+}
+
+// Mantém apenas o nome do arquivo, sem diretórios enviados pelo cliente
+fn sanitize_filename(raw: &str) -> String {
+    let name = raw
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(255)
+        .collect::<String>();
+    if name.is_empty() || name == "." || name == ".." {
+        "arquivo".to_string()
+    } else {
+        name
+    }
+}
+
+// Usa o tipo informado pelo cliente; quando genérico, deduz pela extensão do arquivo
+fn detect_content_type(declared: Option<&mime_guess::Mime>, filename: &str) -> String {
+    match declared {
+        Some(mime) if *mime != mime_guess::mime::APPLICATION_OCTET_STREAM => {
+            mime.essence_str().to_string()
+        }
+        _ => mime_guess::from_path(filename)
+            .first_or_octet_stream()
+            .essence_str()
+            .to_string(),
+    }
+}
+
+// Soma o trecho recebido ao total do envio, recusando o formulário acima do limite
+fn count_upload(total: &mut usize, chunk: usize, max_upload_size: usize) -> Result<(), AppError> {
+    *total += chunk;
+    if *total > max_upload_size {
+        return Err(AppError::PayloadTooLarge(format!(
+            "O envio excede o limite de {} bytes",
+            max_upload_size
+        )));
+    }
+    Ok(())
+}
+
+// Lê os arquivos do formulário multipart aplicando os limites de tamanho e tipo
+async fn read_uploads(
+    mut payload: Multipart,
+    max_size: usize,
+    max_upload_size: usize,
+) -> Result<Vec<Upload>, AppError> {
+    let mut uploads = Vec::new();
+    // Bytes recebidos em todas as partes, inclusive as que não são arquivos
+    let mut total = 0;
+    while let Some(field) = payload.next().await {
+        let mut field =
+            field.map_err(|e| AppError::BadRequest(format!("Formulário inválido: {}", e)))?;
+
+        // Campos sem nome de arquivo não são anexos
+        let Some(filename) = field
+            .content_disposition()
+            .and_then(|cd| cd.get_filename())
+            .map(sanitize_filename)
+        else {
+            while let Some(chunk) = field.next().await {
+                let chunk = chunk
+                    .map_err(|e| AppError::BadRequest(format!("Formulário inválido: {}", e)))?;
+                count_upload(&mut total, chunk.len(), max_upload_size)?;
+            }
+            continue;
+        };
+
+        if uploads.len() == MAX_FILES_PER_UPLOAD {
+            return Err(AppError::validation(format!(
+                "Envie no máximo {} arquivos por vez",
+                MAX_FILES_PER_UPLOAD
+            )));
+        }
+
+        let content_type = detect_content_type(field.content_type(), &filename);
+        if !ALLOWED_TYPES.contains(&content_type.as_str()) {
+            return Err(AppError::UnsupportedMediaType(format!(
+                "Tipo de arquivo não permitido: {}",
+                content_type
+            )));
+        }
+
+        let mut hasher = Sha256::new();
+        let mut data = BytesMut::new();
+        while let Some(chunk) = field.next().await {
+            let chunk = chunk
+                .map_err(|e| AppError::BadRequest(format!("Erro ao receber o arquivo: {}", e)))?;
+            if data.len() + chunk.len() > max_size {
+                return Err(AppError::PayloadTooLarge(format!(
+                    "O arquivo {} excede o limite de {} bytes",
+                    filename, max_size
+                )));
+            }
+            count_upload(&mut total, chunk.len(), max_upload_size)?;
+            hasher.update(&chunk);
+            data.extend_from_slice(&chunk);
+        }
+
+        if data.is_empty() {
+            return Err(AppError::validation(format!(
+                "O arquivo {} está vazio",
+                filename
+            )));
+        }
+
+        uploads.push(Upload {
+            filename,
+            content_type,
+            sha256: hex::encode(hasher.finalize()),
+            data: data.freeze(),
+        });
+    }
+
+    if uploads.is_empty() {
+        return Err(AppError::validation("Nenhum arquivo enviado"));
+    }
+    Ok(uploads)
+}
+
+// Handler para listar os anexos de uma tarefa
+#[utoipa::path(
+    tag = "anexos",
+    summary = "Lista os anexos de uma tarefa",
+    responses(
+        (status = 200, body = ApiResponse<Vec<Attachment>>),
+        (status = 404, body = Message),
+    )
+)]
+#[get("/tasks/{id}/attachments")]
+pub async fn get_attachments(
+    db: web::Data<SqlitePool>,
+    user: AuthUser,
+    path: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    let task_id = path.into_inner();
+    check_task(db.get_ref(), &task_id, &user).await?;
+
+    let attachments = sqlx::query_as::<_, Attachment>(
+        "SELECT * FROM attachments WHERE task_id = ? ORDER BY created_at, id",
+    )
+    .bind(&task_id)
+    .fetch_all(db.get_ref())
+    .await?;
+    Ok(HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        message: "Anexos recuperados com sucesso".to_string(),
+        data: Some(attachments),
+    }))
+}
+
+// Handler para anexar arquivos a uma tarefa (multipart/form-data).
+// Arquivos com o mesmo conteúdo são gravados uma única vez.
+#[utoipa::path(
+    tag = "anexos",
+    summary = "Anexa arquivos a uma tarefa",
+    request_body(content = UploadForm, content_type = "multipart/form-data"),
+    responses(
+        (status = 201, body = ApiResponse<Vec<Attachment>>),
+        (status = 400, description = "Formulário inválido", body = Message),
+        (status = 404, body = Message),
+        (status = 413, description = "Arquivo acima de ATTACHMENT_MAX_SIZE ou envio acima de ATTACHMENT_MAX_UPLOAD_SIZE", body = Message),
+        (status = 415, description = "Tipo de arquivo não permitido", body = Message),
+        (status = 422, description = "Nenhum arquivo, arquivo vazio ou arquivos demais", body = Message),
+    )
+)]
+#[post("/tasks/{id}/attachments")]
+pub async fn upload_attachments(
+    db: web::Data<SqlitePool>,
+    attachments: web::Data<Attachments>,
+    user: AuthUser,
+    path: web::Path<String>,
+    payload: Multipart,
+) -> Result<impl Responder, AppError> {
+    let task_id = path.into_inner();
+    check_task(db.get_ref(), &task_id, &user).await?;
+
+    let uploads = read_uploads(payload, attachments.max_size, attachments.max_upload_size).await?;
+
+    let _guard = attachments.lock.lock().await;
+
+    for upload in &uploads {
+        if let Err(e) = attachments
+            .store
+            .put(&upload.sha256, upload.data.clone())
+            .await
+        {
+            attachments.discard(db.get_ref(), &uploads).await;
+            return Err(AppError::Internal(format!(
+                "Erro ao gravar o arquivo {}: {}",
+                upload.sha256, e
+            )));
+        }
+    }
+
+    let now = Utc::now();
+    let created: Vec<Attachment> = uploads
+        .iter()
+        .map(|upload| Attachment {
+            id: Uuid::new_v4().to_string(),
+            task_id: task_id.clone(),
+            filename: upload.filename.clone(),
+            content_type: upload.content_type.clone(),
+            size: upload.data.len() as i64,
+            sha256: upload.sha256.clone(),
+            uploaded_by: user.id.clone(),
+            created_at: now,
+        })
+        .collect();
+
+    let result: Result<(), sqlx::Error> = async {
+        let mut tx = db.begin().await?;
+        for attachment in &created {
+            sqlx::query(
+                "INSERT OR IGNORE INTO attachment_blobs (sha256, size, created_at) VALUES (?, ?, ?)",
+            )
+            .bind(&attachment.sha256)
+            .bind(attachment.size)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+            sqlx::query(
+                r#"
+                INSERT INTO attachments
+                    (id, task_id, sha256, filename, content_type, size, uploaded_by, created_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&attachment.id)
+            .bind(&attachment.task_id)
+            .bind(&attachment.sha256)
+            .bind(&attachment.filename)
+            .bind(&attachment.content_type)
+            .bind(attachment.size)
+            .bind(&attachment.uploaded_by)
+            .bind(attachment.created_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+    .await;
+
+    // Sem o registro no banco, o conteúdo recém-gravado ficaria órfão no armazenamento
+    if let Err(e) = result {
+        attachments.discard(db.get_ref(), &uploads).await;
+        return Err(e.into());
+    }
+    Ok(HttpResponse::Created().json(ApiResponse {
+        success: true,
+        message: "Anexos enviados com sucesso".to_string(),
+        data: Some(created),
+    }))
+}
+
+// Handler para baixar um anexo com o tipo e o nome de arquivo originais
+#[utoipa::path(
+    tag = "anexos",
+    summary = "Baixa um anexo",
+    responses(
+        (status = 200, description = "Conteúdo do arquivo, com o tipo e o nome originais", content_type = "application/octet-stream", body = Vec<u8>),
+        (status = 404, body = Message),
+    )
+)]
+#[get("/tasks/{id}/attachments/{attachment_id}")]
+pub async fn download_attachment(
+    db: web::Data<SqlitePool>,
+    attachments: web::Data<Attachments>,
+    user: AuthUser,
+    path: web::Path<(String, String)>,
+) -> Result<impl Responder, AppError> {
+    let (task_id, attachment_id) = path.into_inner();
+    check_task(db.get_ref(), &task_id, &user).await?;
+
+    let attachment =
+        sqlx::query_as::<_, Attachment>("SELECT * FROM attachments WHERE task_id = ? AND id = ?")
+            .bind(&task_id)
+            .bind(&attachment_id)
+            .fetch_optional(db.get_ref())
+            .await?
+            .ok_or_else(|| attachment_not_found(&attachment_id))?;
+
+    let data = match attachments.store.get(&attachment.sha256).await {
+        Ok(Some(data)) => data,
+        Ok(None) => {
+            log::error!("Arquivo {} ausente no armazenamento", attachment.sha256);
+            return Err(attachment_not_found(&attachment_id));
+        }
+        Err(e) => {
+            return Err(AppError::Internal(format!(
+                "Erro ao ler o arquivo {}: {}",
+                attachment.sha256, e
+            )))
+        }
+    };
+
+    // Imagens podem ser exibidas no navegador; os demais tipos são sempre baixados
+    let disposition = if attachment.content_type.starts_with("image/") {
+        DispositionType::Inline
+    } else {
+        DispositionType::Attachment
+    };
+    let mut parameters = vec![DispositionParam::Filename(
+        attachment
+            .filename
+            .chars()
+            .map(|c| if c.is_ascii() && c != '"' { c } else { '_' })
+            .collect(),
+    )];
+    if !attachment.filename.is_ascii() {
+        parameters.push(DispositionParam::FilenameExt(ExtendedValue {
+            charset: Charset::Ext("UTF-8".to_string()),
+            language_tag: None,
+            value: attachment.filename.clone().into_bytes(),
+        }));
+    }
+
+    Ok(HttpResponse::Ok()
+        .content_type(attachment.content_type.as_str())
+        .insert_header(ContentDisposition {
+            disposition,
+            parameters,
+        })
+        .insert_header((header::ETAG, format!("\"{}\"", attachment.sha256)))
+        .insert_header((header::X_CONTENT_TYPE_OPTIONS, "nosniff"))
+        .body(data))
+}
+
+// Handler para remover um anexo; o conteúdo é apagado quando nenhum outro anexo o usa
+#[utoipa::path(
+    tag = "anexos",
+    summary = "Remove um anexo",
+    responses(
+        (status = 200, body = Message),
+        (status = 404, body = Message),
+    )
+)]
+#[delete("/tasks/{id}/attachments/{attachment_id}")]
+pub async fn delete_attachment(
+    db: web::Data<SqlitePool>,
+    attachments: web::Data<Attachments>,
+    user: AuthUser,
+    path: web::Path<(String, String)>,
+) -> Result<impl Responder, AppError> {
+    let (task_id, attachment_id) = path.into_inner();
+    check_task(db.get_ref(), &task_id, &user).await?;
+
+    let result = sqlx::query("DELETE FROM attachments WHERE task_id = ? AND id = ?")
+        .bind(&task_id)
+        .bind(&attachment_id)
+        .execute(db.get_ref())
+        .await?;
+    if result.rows_affected() == 0 {
+        return Err(attachment_not_found(&attachment_id));
+    }
+
+    attachments.cleanup(db.get_ref()).await;
+    Ok(HttpResponse::Ok().json(ApiResponse::<()> {
+        success: true,
+        message: format!("Anexo com ID {} excluído com sucesso", attachment_id),
+        data: None,
+    }))
+}
@@ -0,0 +1,298 @@
+use actix_web::{get, web, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{
+    sqlite::{SqliteConnection, SqlitePool},
+    types::Json,
+    FromRow, QueryBuilder, Sqlite,
+};
+use std::collections::BTreeMap;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::auth::AuthUser;
+use crate::error::AppError;
+use crate::events::TaskEventKind;
+use crate::openapi::Message;
+use crate::{check_task, ApiResponse, Task};
+
+// Tamanho padrão e máximo de uma página do histórico
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 200;
+// Campos que não mudam ou mudam a cada gravação, e só poluiriam as diferenças
+const IGNORED_FIELDS: &[&str] = &["id", "owner_id", "created_at", "updated_at", "version"];
+
+// Valor anterior e novo de um campo; `null` quando a tarefa não existia antes ou deixou de existir
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FieldChange {
+    pub old: Value,
+    pub new: Value,
+}
+
+// Registro do histórico de uma tarefa
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct AuditEntry {
+    pub id: i64,
+    pub task_id: String,
+    pub actor_id: String,
+    pub actor_name: Option<String>,
+    pub action: TaskEventKind,
+    #[schema(value_type = BTreeMap<String, FieldChange>)]
+    pub changes: Json<BTreeMap<String, FieldChange>>,
+    pub created_at: DateTime<Utc>,
+}
+
+// Página do histórico, do registro mais recente para o mais antigo
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AuditPage {
+    pub events: Vec<AuditEntry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+// Parâmetros de paginação do histórico
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct AuditQuery {
+    pub limit: Option<i64>,
+    // Valor de `next_cursor` da página anterior
+    pub cursor: Option<String>,
+}
+
+const SELECT_ENTRY: &str = r#"
+    SELECT e.id, e.task_id, e.actor_id, u.username AS actor_name, e.action, e.changes, e.created_at
+    FROM task_events e
+    LEFT JOIN users u ON u.id = e.actor_id
+"#;
+
+// Compara os campos da tarefa antes e depois da alteração
+pub fn diff(before: Option<&Task>, after: Option<&Task>) -> BTreeMap<String, FieldChange> {
+    fn fields(task: Option<&Task>) -> serde_json::Map<String, Value> {
+        match task.map(serde_json::to_value) {
+            Some(Ok(Value::Object(fields))) => fields,
+            _ => serde_json::Map::new(),
+        }
+    }
+
+    let before = fields(before);
+    let mut after = fields(after);
+    let mut changes = BTreeMap::new();
+
+    for (field, old) in before {
+        let new = after.remove(&field).unwrap_or(Value::Null);
+        if old != new {
+            changes.insert(field, FieldChange { old, new });
+        }
+    }
+    for (field, new) in after.into_iter().filter(|(_, new)| !new.is_null()) {
+        changes.insert(
+            field,
+            FieldChange {
+                old: Value::Null,
+                new,
+            },
+        );
+    }
+
+    changes.retain(|field, _| !IGNORED_FIELDS.contains(&field.as_str()));
+    changes
+}
+
+// Registra a alteração no histórico; deve rodar na mesma transação da alteração
+pub async fn record(
+    conn: &mut SqliteConnection,
+    actor_id: &str,
+    action: TaskEventKind,
+    before: Option<&Task>,
+    after: Option<&Task>,
+) -> Result<(), sqlx::Error> {
+    let Some(task) = after.or(before) else {
+        return Ok(());
+    };
+
+    let changes = diff(before, after);
+    // Atualizações que não mudam nenhum campo não geram registro
+    if changes.is_empty() && action == TaskEventKind::Updated {
+        return Ok(());
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO task_events (task_id, owner_id, actor_id, action, changes, created_at)
+        VALUES (?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&task.id)
+    .bind(&task.owner_id)
+    .bind(actor_id)
+    .bind(action)
+    .bind(Json(changes))
+    .bind(Utc::now())
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+// Busca uma página do histórico do usuário, opcionalmente restrita a uma tarefa
+async fn fetch_page(
+    db: &SqlitePool,
+    owner_id: &str,
+    task_id: Option<&str>,
+    query: &AuditQuery,
+) -> Result<AuditPage, AppError> {
+    let cursor = match query.cursor.as_deref().map(str::parse::<i64>) {
+        Some(Ok(cursor)) => Some(cursor),
+        Some(Err(_)) => return Err(AppError::BadRequest("Cursor inválido".to_string())),
+        None => None,
+    };
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    let mut builder = QueryBuilder::<Sqlite>::new(SELECT_ENTRY);
+    builder.push(" WHERE e.owner_id = ").push_bind(owner_id);
+    if let Some(task_id) = task_id {
+        builder.push(" AND e.task_id = ").push_bind(task_id);
+    }
+    if let Some(cursor) = cursor {
+        builder.push(" AND e.id < ").push_bind(cursor);
+    }
+    // Um registro a mais indica que existe próxima página
+    builder
+        .push(" ORDER BY e.id DESC LIMIT ")
+        .push_bind(limit + 1);
+
+    let mut events = builder.build_query_as::<AuditEntry>().fetch_all(db).await?;
+    let next_cursor = if events.len() > limit as usize {
+        events.truncate(limit as usize);
+        events.last().map(|event| event.id.to_string())
+    } else {
+        None
+    };
+
+    Ok(AuditPage {
+        events,
+        next_cursor,
+    })
+}
+
+fn page_response(page: AuditPage) -> HttpResponse {
+    HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        message: "Histórico recuperado com sucesso".to_string(),
+        data: Some(page),
+    })
+}
+
+// Handler para consultar o histórico de alterações de uma tarefa.
+// O histórico continua disponível depois que a tarefa é excluída.
+#[utoipa::path(
+    tag = "histórico",
+    summary = "Histórico de alterações de uma tarefa",
+    params(AuditQuery),
+    responses(
+        (status = 200, body = ApiResponse<AuditPage>),
+        (status = 400, description = "Cursor inválido", body = Message),
+        (status = 404, body = Message),
+    )
+)]
+#[get("/tasks/{id}/history")]
+pub async fn get_task_history(
+    db: web::Data<SqlitePool>,
+    user: AuthUser,
+    path: web::Path<String>,
+    query: web::Query<AuditQuery>,
+) -> Result<impl Responder, AppError> {
+    let task_id = path.into_inner();
+    let page = fetch_page(db.get_ref(), &user.id, Some(&task_id), &query).await?;
+
+    // Sem registros, distingue uma tarefa sem histórico de uma tarefa inexistente
+    if page.events.is_empty() && query.cursor.is_none() {
+        check_task(db.get_ref(), &task_id, &user).await?;
+    }
+    Ok(page_response(page))
+}
+
+// Handler para o feed de atividades com as alterações em todas as tarefas do usuário
+#[utoipa::path(
+    tag = "histórico",
+    summary = "Feed de atividades de todas as tarefas do usuário",
+    params(AuditQuery),
+    responses(
+        (status = 200, body = ApiResponse<AuditPage>),
+        (status = 400, description = "Cursor inválido", body = Message),
+    )
+)]
+#[get("/activity")]
+pub async fn get_activity(
+    db: web::Data<SqlitePool>,
+    user: AuthUser,
+    query: web::Query<AuditQuery>,
+) -> Result<impl Responder, AppError> {
+    Ok(page_response(
+        fetch_page(db.get_ref(), &user.id, None, &query).await?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task() -> Task {
+        serde_json::from_value(serde_json::json!({
+            "id": "3f1c2b9e-0000-4000-8000-000000000001",
+            "title": "Relatório",
+            "description": "Fechar o mês",
+            "priority": 2,
+            "owner_id": "dono",
+            "version": 4,
+            "created_at": "2024-03-01T10:00:00Z",
+            "updated_at": "2024-03-02T11:30:00Z",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn create_records_only_the_new_values() {
+        let task = task();
+        let changes = diff(None, Some(&task));
+        assert_eq!(changes["title"].old, Value::Null);
+        assert_eq!(changes["title"].new, "Relatório");
+        assert_eq!(changes["priority"].new, 2);
+        assert!(changes.values().all(|change| change.old.is_null()));
+        // Campos vazios e os que mudam a cada gravação ficam de fora
+        assert!(!changes.contains_key("due_at"));
+        for field in IGNORED_FIELDS {
+            assert!(!changes.contains_key(*field), "{}", field);
+        }
+    }
+
+    #[test]
+    fn delete_records_only_the_old_values() {
+        let task = task();
+        let changes = diff(Some(&task), None);
+        assert_eq!(changes["description"].old, "Fechar o mês");
+        assert_eq!(changes["description"].new, Value::Null);
+        assert!(changes.values().all(|change| change.new.is_null()));
+        assert!(!changes.contains_key("due_at"));
+        assert!(!changes.contains_key("version"));
+    }
+
+    #[test]
+    fn update_of_version_and_timestamp_only_is_empty() {
+        let before = task();
+        let mut after = before.clone();
+        after.version += 1;
+        after.updated_at = Utc::now();
+        assert!(diff(Some(&before), Some(&after)).is_empty());
+    }
+
+    #[test]
+    fn update_records_changed_fields() {
+        let before = task();
+        let mut after = before.clone();
+        after.title = "Relatório final".to_string();
+        after.version += 1;
+        let changes = diff(Some(&before), Some(&after));
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes["title"].old, "Relatório");
+        assert_eq!(changes["title"].new, "Relatório final");
+    }
+}
@@ -0,0 +1,387 @@
+use actix_web::{
+    body::MessageBody,
+    dev::{Payload, ServiceRequest, ServiceResponse},
+    get,
+    middleware::Next,
+    post, web, FromRequest, HttpMessage, HttpRequest, HttpResponse, Responder, Result,
+};
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use sqlx::{sqlite::SqlitePool, FromRow};
+use std::collections::HashMap;
+use std::env;
+use std::future::{ready, Ready};
+use std::sync::LazyLock;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::openapi::Message;
+use crate::ApiResponse;
+
+// Modelo de usuário
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct User {
+    pub id: String,
+    pub username: String,
+    #[serde(skip_serializing)]
+    pub password_hash: String,
+    pub created_at: DateTime<Utc>,
+}
+
+// Dados de cadastro e login
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct Credentials {
+    username: String,
+    password: String,
+}
+
+// Resposta de cadastro e login
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AuthResponse {
+    token: String,
+    user: User,
+}
+
+// Conteúdo do token JWT
+#[derive(Debug, Serialize, Deserialize)]
+struct Claims {
+    sub: String,
+    exp: i64,
+}
+
+// Chaves e validade dos tokens, lidas do arquivo .env
+pub struct AuthConfig {
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+    ttl: Duration,
+}
+
+impl AuthConfig {
+    pub fn from_env() -> Self {
+        let secret = env::var("JWT_SECRET").expect("JWT_SECRET não definida");
+        let hours = env::var("JWT_EXPIRATION_HOURS")
+            .unwrap_or_else(|_| "24".to_string())
+            .parse::<i64>()
+            .expect("JWT_EXPIRATION_HOURS deve ser um número");
+
+        AuthConfig {
+            encoding: EncodingKey::from_secret(secret.as_bytes()),
+            decoding: DecodingKey::from_secret(secret.as_bytes()),
+            ttl: Duration::hours(hours),
+        }
+    }
+
+    fn issue(&self, user_id: &str) -> Result<String, jsonwebtoken::errors::Error> {
+        let claims = Claims {
+            sub: user_id.to_string(),
+            exp: (Utc::now() + self.ttl).timestamp(),
+        };
+        encode(&Header::default(), &claims, &self.encoding)
+    }
+
+    fn verify(&self, token: &str) -> Result<String, jsonwebtoken::errors::Error> {
+        decode::<Claims>(token, &self.decoding, &Validation::default()).map(|data| data.claims.sub)
+    }
+}
+
+// Usuário autenticado da requisição atual.
+// Handlers que recebem este extrator respondem 401 quando não há token válido.
+#[derive(Debug, Clone)]
+pub struct AuthUser {
+    pub id: String,
+}
+
+impl FromRequest for AuthUser {
+    type Error = AppError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        ready(
+            req.extensions()
+                .get::<AuthUser>()
+                .cloned()
+                .ok_or_else(|| AppError::Unauthorized("Autenticação necessária".to_string())),
+        )
+    }
+}
+
+// Extrai o token do cabeçalho Authorization ou, para clientes que não
+// conseguem enviar cabeçalhos (como o EventSource), do parâmetro access_token
+fn extract_token(req: &ServiceRequest) -> Option<String> {
+    if let Some(value) = req.headers().get("Authorization") {
+        return value
+            .to_str()
+            .ok()
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(|token| token.trim().to_string());
+    }
+
+    web::Query::<HashMap<String, String>>::from_query(req.query_string())
+        .ok()
+        .and_then(|query| query.get("access_token").cloned())
+}
+
+// Middleware que valida o token e registra o usuário na requisição.
+// Requisições sem token seguem adiante; rotas protegidas são barradas pelo extrator AuthUser.
+pub async fn authenticate(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>> {
+    if let Some(token) = extract_token(&req) {
+        let config = req
+            .app_data::<web::Data<AuthConfig>>()
+            .expect("AuthConfig não registrada");
+
+        match config.verify(&token) {
+            Ok(user_id) => {
+                req.extensions_mut().insert(AuthUser { id: user_id });
+            }
+            Err(_) => {
+                return Err(AppError::Unauthorized("Token inválido ou expirado".to_string()).into())
+            }
+        }
+    }
+
+    next.call(req).await
+}
+
+// Gera o hash Argon2 da senha fora das threads do servidor
+async fn hash_password(password: String) -> Result<String, String> {
+    web::block(move || {
+        let salt = SaltString::generate(&mut OsRng);
+        Argon2::default()
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Hash de uma senha qualquer, verificado quando o usuário não existe (ou não tem senha)
+// para que o login leve o mesmo tempo e não revele quais usuários estão cadastrados
+static DUMMY_HASH: LazyLock<String> = LazyLock::new(|| {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(Uuid::new_v4().as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .unwrap_or_default()
+});
+
+async fn verify_password(password: String, hash: Option<String>) -> bool {
+    web::block(move || {
+        let verify = |hash: &str| {
+            PasswordHash::new(hash)
+                .map(|parsed| {
+                    Argon2::default()
+                        .verify_password(password.as_bytes(), &parsed)
+                        .is_ok()
+                })
+                .unwrap_or(false)
+        };
+        match hash.filter(|hash| PasswordHash::new(hash).is_ok()) {
+            Some(hash) => verify(&hash),
+            None => {
+                verify(&DUMMY_HASH);
+                false
+            }
+        }
+    })
+    .await
+    .unwrap_or(false)
+}
+
+// Usuário criado pela migração 0018 para as tarefas anteriores às contas de usuário
+const LEGACY_USER_ID: &str = "00000000-0000-0000-0000-000000000000";
+
+// O usuário legado nasce sem senha, e as tarefas dele ficam inacessíveis até que
+// LEGACY_USER_PASSWORD defina uma. Sem a variável, o servidor avisa no log ao iniciar.
+pub async fn init_legacy_user(pool: &SqlitePool) -> Result<(), String> {
+    let tasks: Option<i64> = sqlx::query_scalar(
+        r#"
+        SELECT (SELECT COUNT(*) FROM tasks WHERE owner_id = users.id)
+        FROM users WHERE id = ? AND password_hash = ''
+        "#,
+    )
+    .bind(LEGACY_USER_ID)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    let Some(tasks) = tasks else {
+        return Ok(());
+    };
+
+    match env::var("LEGACY_USER_PASSWORD") {
+        Ok(password) if password.chars().count() >= 8 => {
+            let password_hash = hash_password(password).await?;
+            sqlx::query("UPDATE users SET password_hash = ? WHERE id = ?")
+                .bind(password_hash)
+                .bind(LEGACY_USER_ID)
+                .execute(pool)
+                .await
+                .map_err(|e| e.to_string())?;
+            log::info!("Senha do usuário legado definida");
+        }
+        Ok(_) => return Err("LEGACY_USER_PASSWORD deve ter pelo menos 8 caracteres".to_string()),
+        Err(_) => log::warn!(
+            "{} tarefa(s) criadas antes das contas de usuário pertencem ao usuário legado, \
+             que ainda não tem senha. Defina LEGACY_USER_PASSWORD para entrar com ele",
+            tasks
+        ),
+    }
+    Ok(())
+}
+
+fn token_response(
+    config: &AuthConfig,
+    user: User,
+    status: actix_web::http::StatusCode,
+    message: &str,
+) -> Result<HttpResponse, AppError> {
+    let token = config
+        .issue(&user.id)
+        .map_err(|e| AppError::Internal(format!("Erro ao gerar token: {}", e)))?;
+    Ok(HttpResponse::build(status).json(ApiResponse {
+        success: true,
+        message: message.to_string(),
+        data: Some(AuthResponse { token, user }),
+    }))
+}
+
+// Handler para cadastrar um novo usuário
+#[utoipa::path(
+    tag = "autenticação",
+    summary = "Cadastra um usuário",
+    security(()),
+    request_body = Credentials,
+    responses(
+        (status = 201, description = "Usuário cadastrado, com o token de acesso", body = ApiResponse<AuthResponse>),
+        (status = 409, description = "Nome de usuário em uso", body = Message),
+        (status = 422, description = "Nome de usuário ou senha inválidos", body = Message),
+    )
+)]
+#[post("/auth/register")]
+pub async fn register(
+    db: web::Data<SqlitePool>,
+    config: web::Data<AuthConfig>,
+    credentials: web::Json<Credentials>,
+) -> Result<impl Responder, AppError> {
+    let credentials = credentials.into_inner();
+    let username = credentials.username.trim().to_string();
+
+    if !(3..=50).contains(&username.chars().count()) {
+        return Err(AppError::validation(
+            "O nome de usuário deve ter entre 3 e 50 caracteres",
+        ));
+    }
+    if credentials.password.chars().count() < 8 {
+        return Err(AppError::validation(
+            "A senha deve ter pelo menos 8 caracteres",
+        ));
+    }
+
+    let password_hash = hash_password(credentials.password)
+        .await
+        .map_err(|e| AppError::Internal(format!("Erro ao gerar hash da senha: {}", e)))?;
+
+    let user = User {
+        id: Uuid::new_v4().to_string(),
+        username,
+        password_hash,
+        created_at: Utc::now(),
+    };
+
+    match sqlx::query(
+        "INSERT INTO users (id, username, password_hash, created_at) VALUES (?, ?, ?, ?)",
+    )
+    .bind(&user.id)
+    .bind(&user.username)
+    .bind(&user.password_hash)
+    .bind(user.created_at)
+    .execute(db.get_ref())
+    .await
+    {
+        Ok(_) => token_response(
+            &config,
+            user,
+            actix_web::http::StatusCode::CREATED,
+            "Usuário cadastrado com sucesso",
+        ),
+        Err(e)
+            if e.as_database_error()
+                .map(|db_err| db_err.is_unique_violation())
+                .unwrap_or(false) =>
+        {
+            Err(AppError::Conflict(format!(
+                "O usuário {} já existe",
+                user.username
+            )))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+// Handler para autenticar um usuário e emitir o token de acesso
+#[utoipa::path(
+    tag = "autenticação",
+    summary = "Autentica um usuário e emite o token de acesso",
+    security(()),
+    request_body = Credentials,
+    responses(
+        (status = 200, body = ApiResponse<AuthResponse>),
+        (status = 401, description = "Usuário ou senha incorretos", body = Message),
+    )
+)]
+#[post("/auth/login")]
+pub async fn login(
+    db: web::Data<SqlitePool>,
+    config: web::Data<AuthConfig>,
+    credentials: web::Json<Credentials>,
+) -> Result<impl Responder, AppError> {
+    let credentials = credentials.into_inner();
+
+    let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE username = ?")
+        .bind(credentials.username.trim())
+        .fetch_optional(db.get_ref())
+        .await?;
+    let hash = user.as_ref().map(|user| user.password_hash.clone());
+    let valid = verify_password(credentials.password, hash).await;
+
+    match user {
+        Some(user) if valid => token_response(
+            &config,
+            user,
+            actix_web::http::StatusCode::OK,
+            "Login realizado com sucesso",
+        ),
+        _ => Err(AppError::Unauthorized(
+            "Usuário ou senha inválidos".to_string(),
+        )),
+    }
+}
+
+// Handler para obter os dados do usuário autenticado
+#[utoipa::path(
+    tag = "autenticação",
+    summary = "Dados do usuário autenticado",
+    responses((status = 200, body = ApiResponse<User>))
+)]
+#[get("/auth/me")]
+pub async fn me(db: web::Data<SqlitePool>, user: AuthUser) -> Result<impl Responder, AppError> {
+    let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = ?")
+        .bind(&user.id)
+        .fetch_optional(db.get_ref())
+        .await?
+        .ok_or_else(|| AppError::Unauthorized("Usuário não encontrado".to_string()))?;
+    Ok(HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        message: "Usuário recuperado com sucesso".to_string(),
+        data: Some(user),
+    }))
+}
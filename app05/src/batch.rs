@@ -0,0 +1,459 @@
+use actix_web::{http::StatusCode, post, web, HttpResponse, Responder};
+use serde::{Deserialize, Serialize};
+use sqlx::{sqlite::SqliteConnection, sqlite::SqlitePool, Connection};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::attachments::Attachments;
+use crate::auth::AuthUser;
+use crate::dependencies::TaskNode;
+use crate::error::AppError;
+use crate::events::{TaskEventKind, TaskEvents};
+use crate::openapi::Message;
+use crate::reminders::Reminders;
+use crate::validation;
+use crate::{
+    apply_update, check_new_task, delete_task_tree, find_task, insert_task, prepare_new_task,
+    save_update, ApiResponse, Task, TaskUpdate, UpdateRejection,
+};
+
+// Quantidade máxima de operações em um lote
+const MAX_BATCH_SIZE: usize = 100;
+
+// Como o lote reage a uma operação que falha
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchMode {
+    // A primeira falha desfaz o lote inteiro
+    #[default]
+    AllOrNothing,
+    // Cada operação é aplicada ou descartada individualmente
+    BestEffort,
+}
+
+// Operação do lote, identificada pelo campo `op`
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum BatchOperation {
+    Create {
+        task: Task,
+    },
+    Update {
+        id: String,
+        changes: TaskUpdate,
+        // Versão esperada da tarefa, como no If-Match
+        #[serde(default)]
+        version: Option<i64>,
+    },
+    Delete {
+        id: String,
+        #[serde(default)]
+        version: Option<i64>,
+    },
+}
+
+impl BatchOperation {
+    fn name(&self) -> &'static str {
+        match self {
+            BatchOperation::Create { .. } => "create",
+            BatchOperation::Update { .. } => "update",
+            BatchOperation::Delete { .. } => "delete",
+        }
+    }
+}
+
+// Corpo de POST /tasks/batch
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BatchRequest {
+    #[serde(default)]
+    mode: BatchMode,
+    operations: Vec<BatchOperation>,
+}
+
+// Resultado de uma operação, na mesma ordem do pedido
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BatchItemResult {
+    pub index: usize,
+    pub op: &'static str,
+    pub success: bool,
+    // Código HTTP que a operação teria na rota individual
+    pub status: u16,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Task>,
+    // Bloqueadoras em aberto, quando a operação tentou concluir uma tarefa bloqueada
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blockers: Option<Vec<TaskNode>>,
+}
+
+// Resumo do lote devolvido no campo `data`
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BatchSummary {
+    pub mode: BatchMode,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub results: Vec<BatchItemResult>,
+}
+
+// Eventos e reagendamentos a disparar depois do commit, só para o que foi gravado
+#[derive(Default)]
+pub struct Effects {
+    events: Vec<(TaskEventKind, Task)>,
+    reschedule: bool,
+}
+
+impl Effects {
+    pub fn created(&mut self, task: Task) {
+        self.reschedule |= task.remind_at.is_some();
+        self.events.push((TaskEventKind::Created, task));
+    }
+
+    pub fn updated(&mut self, before: &Task, task: Task, next: Option<Task>) {
+        self.reschedule |= task.remind_at != before.remind_at;
+        self.events.push((TaskEventKind::Updated, task));
+        if let Some(next) = next {
+            self.created(next);
+        }
+    }
+
+    pub fn deleted(&mut self, tasks: Vec<Task>) {
+        self.events
+            .extend(tasks.into_iter().map(|task| (TaskEventKind::Deleted, task)));
+    }
+
+    pub fn extend(&mut self, other: Effects) {
+        self.events.extend(other.events);
+        self.reschedule |= other.reschedule;
+    }
+
+    // Publica os eventos, reagenda os lembretes e apaga os arquivos de anexos órfãos
+    pub async fn dispatch(
+        self,
+        db: &SqlitePool,
+        events: &TaskEvents,
+        reminders: &Reminders,
+        attachments: &Attachments,
+    ) {
+        let mut deleted = false;
+        for (kind, task) in self.events {
+            deleted |= kind == TaskEventKind::Deleted;
+            events.publish(kind, task);
+        }
+        if self.reschedule {
+            reminders.reschedule();
+        }
+        if deleted {
+            // Os anexos saem junto com as tarefas (ON DELETE CASCADE); resta apagar os arquivos
+            attachments.cleanup(db).await;
+        }
+    }
+}
+
+// Operação aplicada, com os efeitos a disparar depois do commit
+struct Applied {
+    status: StatusCode,
+    message: String,
+    data: Option<Task>,
+    effects: Effects,
+}
+
+// Operação recusada; nada do que ela fez é mantido
+struct Rejected {
+    status: StatusCode,
+    message: String,
+    data: Option<Task>,
+    blockers: Option<Vec<TaskNode>>,
+}
+
+impl Rejected {
+    fn new(status: StatusCode, message: String) -> Self {
+        Rejected {
+            status,
+            message,
+            data: None,
+            blockers: None,
+        }
+    }
+
+    fn not_found(id: &str) -> Self {
+        Self::new(
+            StatusCode::NOT_FOUND,
+            format!("Tarefa com ID {} não encontrada", id),
+        )
+    }
+
+    fn version_mismatch(task: Task) -> Self {
+        Rejected {
+            data: Some(task),
+            ..Self::new(
+                StatusCode::PRECONDITION_FAILED,
+                "A versão informada não é a atual".to_string(),
+            )
+        }
+    }
+}
+
+// Executa uma operação na conexão recebida (um savepoint do lote)
+async fn execute(
+    conn: &mut SqliteConnection,
+    owner_id: &str,
+    operation: BatchOperation,
+) -> Result<Result<Applied, Rejected>, sqlx::Error> {
+    match operation {
+        BatchOperation::Create { task } => {
+            // As operações do lote não passam pelo ValidJson: as regras dos campos valem aqui
+            let prepared = task
+                .validate()
+                .map_err(|errors| validation::summary(&errors))
+                .and_then(|_| {
+                    prepare_new_task(task, Uuid::new_v4().to_string(), owner_id.to_string())
+                });
+            let mut new_task = match prepared {
+                Ok(new_task) => new_task,
+                Err(message) => {
+                    return Ok(Err(Rejected::new(
+                        StatusCode::UNPROCESSABLE_ENTITY,
+                        message,
+                    )))
+                }
+            };
+            if let Err(message) = check_new_task(conn, &mut new_task).await? {
+                return Ok(Err(Rejected::new(
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    message,
+                )));
+            }
+            insert_task(conn, &new_task).await?;
+            let mut effects = Effects::default();
+            effects.created(new_task.clone());
+            Ok(Ok(Applied {
+                status: StatusCode::CREATED,
+                message: "Tarefa criada com sucesso".to_string(),
+                data: Some(new_task),
+                effects,
+            }))
+        }
+        BatchOperation::Update {
+            id,
+            changes,
+            version,
+        } => {
+            let Some(existing) = find_task(&mut *conn, &id, owner_id).await? else {
+                return Ok(Err(Rejected::not_found(&id)));
+            };
+            if version.is_some_and(|version| version != existing.version) {
+                return Ok(Err(Rejected::version_mismatch(existing)));
+            }
+            let target = match changes
+                .validate()
+                .map_err(|errors| validation::summary(&errors))
+                .and_then(|_| apply_update(&existing, &changes))
+            {
+                Ok(target) => target,
+                Err(message) => {
+                    return Ok(Err(Rejected::new(
+                        StatusCode::UNPROCESSABLE_ENTITY,
+                        message,
+                    )))
+                }
+            };
+            // O lote já detém o bloqueio de escrita, então a versão lida não muda até a gravação
+            match save_update(conn, owner_id, &existing, &target).await? {
+                Ok((updated, next)) => {
+                    let mut effects = Effects::default();
+                    effects.updated(&existing, updated.clone(), next);
+                    Ok(Ok(Applied {
+                        status: StatusCode::OK,
+                        message: "Tarefa atualizada com sucesso".to_string(),
+                        data: Some(updated),
+                        effects,
+                    }))
+                }
+                Err(UpdateRejection::Stale) => Ok(Err(Rejected::new(
+                    StatusCode::CONFLICT,
+                    format!("A tarefa com ID {} foi modificada por outra requisição", id),
+                ))),
+                Err(UpdateRejection::Blocked(blockers)) => Ok(Err(Rejected {
+                    blockers: Some(blockers.clone()),
+                    ..Rejected::new(
+                        StatusCode::CONFLICT,
+                        format!(
+                            "A tarefa depende de {} tarefa(s) não finalizada(s)",
+                            blockers.len()
+                        ),
+                    )
+                })),
+                Err(UpdateRejection::Invalid(message)) => Ok(Err(Rejected::new(
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    message,
+                ))),
+            }
+        }
+        BatchOperation::Delete { id, version } => {
+            let versions = version.as_ref().map(std::slice::from_ref);
+            let deleted = delete_task_tree(conn, owner_id, &id, owner_id, versions).await?;
+            if deleted.is_empty() {
+                // Distingue a tarefa inexistente da versão que não confere
+                return match find_task(&mut *conn, &id, owner_id).await? {
+                    Some(task) => Ok(Err(Rejected::version_mismatch(task))),
+                    None => Ok(Err(Rejected::not_found(&id))),
+                };
+            }
+            let mut effects = Effects::default();
+            effects.deleted(deleted);
+            Ok(Ok(Applied {
+                status: StatusCode::OK,
+                message: format!("Tarefa com ID {} excluída com sucesso", id),
+                data: None,
+                effects,
+            }))
+        }
+    }
+}
+
+// Handler para aplicar várias criações, alterações e exclusões em uma única transação.
+// Em all_or_nothing (padrão), a primeira falha desfaz o lote; em best_effort, cada
+// operação roda em um savepoint próprio e só as que falharem são descartadas.
+#[utoipa::path(
+    tag = "tarefas",
+    summary = "Aplica várias criações, alterações e exclusões em uma transação",
+    request_body = BatchRequest,
+    responses(
+        (status = 200, description = "Lote aplicado; em best_effort, success é false se alguma operação falhou", body = ApiResponse<BatchSummary>),
+        (status = "4XX", description = "Lote all_or_nothing desfeito, com o código da operação que falhou", body = ApiResponse<BatchSummary>),
+        (status = 422, description = "Lote vazio ou com operações demais", body = Message),
+    )
+)]
+#[post("/tasks/batch")]
+pub async fn batch_tasks(
+    db: web::Data<SqlitePool>,
+    events: web::Data<TaskEvents>,
+    reminders: web::Data<Reminders>,
+    attachments: web::Data<Attachments>,
+    user: AuthUser,
+    request: web::Json<BatchRequest>,
+) -> Result<impl Responder, AppError> {
+    let BatchRequest { mode, operations } = request.into_inner();
+    if operations.is_empty() || operations.len() > MAX_BATCH_SIZE {
+        return Err(AppError::validation(format!(
+            "O lote deve ter entre 1 e {} operações",
+            MAX_BATCH_SIZE
+        )));
+    }
+    let total = operations.len();
+    let names: Vec<&'static str> = operations.iter().map(BatchOperation::name).collect();
+
+    let result: Result<(Vec<BatchItemResult>, Effects), sqlx::Error> = async {
+        let mut tx = db.begin().await?;
+        // Obtém o bloqueio de escrita antes da primeira leitura, para que nenhuma outra
+        // gravação se intercale com as operações do lote
+        sqlx::query("UPDATE tasks SET version = version WHERE 0")
+            .execute(&mut *tx)
+            .await?;
+
+        let mut results = Vec::with_capacity(total);
+        let mut effects = Effects::default();
+        let mut aborted = false;
+        for (index, operation) in operations.into_iter().enumerate() {
+            let op = names[index];
+            let mut savepoint = tx.begin().await?;
+            match execute(&mut savepoint, &user.id, operation).await? {
+                Ok(done) => {
+                    savepoint.commit().await?;
+                    results.push(BatchItemResult {
+                        index,
+                        op,
+                        success: true,
+                        status: done.status.as_u16(),
+                        message: done.message,
+                        data: done.data,
+                        blockers: None,
+                    });
+                    effects.extend(done.effects);
+                }
+                Err(rejected) => {
+                    savepoint.rollback().await?;
+                    results.push(BatchItemResult {
+                        index,
+                        op,
+                        success: false,
+                        status: rejected.status.as_u16(),
+                        message: rejected.message,
+                        data: rejected.data,
+                        blockers: rejected.blockers,
+                    });
+                    if mode == BatchMode::AllOrNothing {
+                        aborted = true;
+                        break;
+                    }
+                }
+            }
+        }
+
+        // Sem commit, a transação é desfeita ao sair do escopo
+        if aborted {
+            effects = Effects::default();
+        } else {
+            tx.commit().await?;
+        }
+        Ok((results, effects))
+    }
+    .await;
+
+    let (mut results, effects) = result?;
+    effects
+        .dispatch(db.get_ref(), &events, &reminders, &attachments)
+        .await;
+
+    let failed_item = results.iter().position(|item| !item.success);
+    if let (BatchMode::AllOrNothing, Some(failed_index)) = (mode, failed_item) {
+        let failed = &results[failed_index];
+        let status = StatusCode::from_u16(failed.status).unwrap_or(StatusCode::CONFLICT);
+        let message = format!(
+            "Lote desfeito: a operação {} falhou: {}",
+            failed_index, failed.message
+        );
+
+        // As operações anteriores foram desfeitas e as seguintes não chegaram a rodar
+        for item in &mut results[..failed_index] {
+            item.success = false;
+            item.status = StatusCode::FAILED_DEPENDENCY.as_u16();
+            item.message = format!("Desfeita porque a operação {} falhou", failed_index);
+            item.data = None;
+        }
+        let skipped = names.iter().enumerate().skip(failed_index + 1);
+        results.extend(skipped.map(|(index, &op)| BatchItemResult {
+            index,
+            op,
+            success: false,
+            status: StatusCode::FAILED_DEPENDENCY.as_u16(),
+            message: format!("Não executada porque a operação {} falhou", failed_index),
+            data: None,
+            blockers: None,
+        }));
+
+        return Ok(HttpResponse::build(status).json(ApiResponse {
+            success: false,
+            message,
+            data: Some(BatchSummary {
+                mode,
+                succeeded: 0,
+                failed: total,
+                results,
+            }),
+        }));
+    }
+
+    let failed = results.iter().filter(|item| !item.success).count();
+    Ok(HttpResponse::Ok().json(ApiResponse {
+        success: failed == 0,
+        message: format!("{} de {} operação(ões) aplicada(s)", total - failed, total),
+        data: Some(BatchSummary {
+            mode,
+            succeeded: total - failed,
+            failed,
+            results,
+        }),
+    }))
+}
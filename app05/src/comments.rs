@@ -0,0 +1,363 @@
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{sqlite::SqlitePool, FromRow};
+use std::collections::HashMap;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::auth::AuthUser;
+use crate::error::AppError;
+use crate::openapi::Message;
+use crate::{check_task, ApiResponse};
+
+// Tamanho máximo do texto de um comentário
+const MAX_BODY_LENGTH: usize = 10_000;
+
+// Modelo de comentário
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct Comment {
+    pub id: String,
+    pub task_id: String,
+    pub author_id: String,
+    pub author_name: String,
+    pub parent_id: Option<String>,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    // Comentários excluídos viram marcadores para não quebrar as respostas
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub edited: bool,
+    #[sqlx(skip)]
+    #[schema(no_recursion)]
+    pub replies: Vec<Comment>,
+}
+
+// Versão anterior de um comentário editado
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct CommentRevision {
+    pub body: String,
+    pub edited_at: DateTime<Utc>,
+}
+
+// Dados para criar um comentário
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct NewComment {
+    body: String,
+    // Comentário ao qual este responde
+    parent_id: Option<String>,
+}
+
+// Dados para editar um comentário
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CommentUpdate {
+    body: String,
+}
+
+const SELECT_COMMENT: &str = r#"
+    SELECT c.id, c.task_id, c.author_id, u.username AS author_name, c.parent_id,
+           c.body, c.created_at, c.updated_at, c.deleted_at,
+           EXISTS (SELECT 1 FROM comment_revisions r WHERE r.comment_id = c.id) AS edited
+    FROM comments c
+    JOIN users u ON u.id = c.author_id
+"#;
+
+async fn find_comment(
+    db: &SqlitePool,
+    task_id: &str,
+    comment_id: &str,
+) -> Result<Option<Comment>, sqlx::Error> {
+    sqlx::query_as::<_, Comment>(&format!(
+        "{} WHERE c.task_id = ? AND c.id = ?",
+        SELECT_COMMENT
+    ))
+    .bind(task_id)
+    .bind(comment_id)
+    .fetch_optional(db)
+    .await
+}
+
+// Organiza a lista plana de comentários em árvore, mantendo a ordem cronológica
+fn build_threads(comments: Vec<Comment>) -> Vec<Comment> {
+    let mut children: HashMap<Option<String>, Vec<Comment>> = HashMap::new();
+    for comment in comments {
+        children
+            .entry(comment.parent_id.clone())
+            .or_default()
+            .push(comment);
+    }
+
+    fn attach(
+        parent: Option<String>,
+        children: &mut HashMap<Option<String>, Vec<Comment>>,
+    ) -> Vec<Comment> {
+        let mut level = children.remove(&parent).unwrap_or_default();
+        for comment in &mut level {
+            comment.replies = attach(Some(comment.id.clone()), children);
+        }
+        level
+    }
+
+    attach(None, &mut children)
+}
+
+fn validate_body(body: &str) -> Result<(), AppError> {
+    let message = if body.trim().is_empty() {
+        "O comentário não pode ser vazio"
+    } else if body.chars().count() > MAX_BODY_LENGTH {
+        "O comentário não pode exceder 10000 caracteres"
+    } else {
+        return Ok(());
+    };
+    Err(AppError::validation(message))
+}
+
+fn comment_not_found(id: &str) -> AppError {
+    AppError::NotFound(format!("Comentário com ID {} não encontrado", id))
+}
+
+// Handler para listar os comentários de uma tarefa, organizados em threads
+#[utoipa::path(
+    tag = "comentários",
+    summary = "Lista os comentários de uma tarefa em threads",
+    responses(
+        (status = 200, body = ApiResponse<Vec<Comment>>),
+        (status = 404, body = Message),
+    )
+)]
+#[get("/tasks/{id}/comments")]
+pub async fn get_comments(
+    db: web::Data<SqlitePool>,
+    user: AuthUser,
+    path: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    let task_id = path.into_inner();
+    check_task(db.get_ref(), &task_id, &user).await?;
+
+    let comments = sqlx::query_as::<_, Comment>(&format!(
+        "{} WHERE c.task_id = ? ORDER BY c.created_at, c.id",
+        SELECT_COMMENT
+    ))
+    .bind(&task_id)
+    .fetch_all(db.get_ref())
+    .await?;
+    Ok(HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        message: "Comentários recuperados com sucesso".to_string(),
+        data: Some(build_threads(comments)),
+    }))
+}
+
+// Handler para comentar em uma tarefa ou responder a um comentário
+#[utoipa::path(
+    tag = "comentários",
+    summary = "Comenta em uma tarefa ou responde a um comentário",
+    request_body = NewComment,
+    responses(
+        (status = 201, body = ApiResponse<Comment>),
+        (status = 404, body = Message),
+        (status = 422, description = "Texto vazio ou longo demais, ou comentário respondido inexistente", body = Message),
+    )
+)]
+#[post("/tasks/{id}/comments")]
+pub async fn create_comment(
+    db: web::Data<SqlitePool>,
+    user: AuthUser,
+    path: web::Path<String>,
+    input: web::Json<NewComment>,
+) -> Result<impl Responder, AppError> {
+    let task_id = path.into_inner();
+    let input = input.into_inner();
+
+    validate_body(&input.body)?;
+    check_task(db.get_ref(), &task_id, &user).await?;
+
+    // A resposta precisa apontar para um comentário da mesma tarefa
+    if let Some(parent_id) = &input.parent_id {
+        if find_comment(db.get_ref(), &task_id, parent_id)
+            .await?
+            .is_none()
+        {
+            return Err(AppError::validation(format!(
+                "Comentário com ID {} não encontrado na tarefa",
+                parent_id
+            )));
+        }
+    }
+
+    let id = Uuid::new_v4().to_string();
+    let now = Utc::now();
+    sqlx::query(
+        r#"
+        INSERT INTO comments (id, task_id, author_id, parent_id, body, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&id)
+    .bind(&task_id)
+    .bind(&user.id)
+    .bind(&input.parent_id)
+    .bind(&input.body)
+    .bind(now)
+    .bind(now)
+    .execute(db.get_ref())
+    .await?;
+
+    let comment = find_comment(db.get_ref(), &task_id, &id)
+        .await?
+        .ok_or_else(|| comment_not_found(&id))?;
+    Ok(HttpResponse::Created().json(ApiResponse {
+        success: true,
+        message: "Comentário criado com sucesso".to_string(),
+        data: Some(comment),
+    }))
+}
+
+// Handler para editar um comentário; o texto anterior vai para o histórico
+#[utoipa::path(
+    tag = "comentários",
+    summary = "Edita um comentário",
+    request_body = CommentUpdate,
+    responses(
+        (status = 200, body = ApiResponse<Comment>),
+        (status = 403, description = "Apenas o autor pode editar o comentário", body = Message),
+        (status = 404, body = Message),
+        (status = 422, description = "Texto vazio ou longo demais", body = Message),
+    )
+)]
+#[put("/tasks/{id}/comments/{comment_id}")]
+pub async fn update_comment(
+    db: web::Data<SqlitePool>,
+    user: AuthUser,
+    path: web::Path<(String, String)>,
+    input: web::Json<CommentUpdate>,
+) -> Result<impl Responder, AppError> {
+    let (task_id, comment_id) = path.into_inner();
+    let input = input.into_inner();
+
+    validate_body(&input.body)?;
+    check_task(db.get_ref(), &task_id, &user).await?;
+
+    let comment = find_comment(db.get_ref(), &task_id, &comment_id)
+        .await?
+        .filter(|comment| comment.deleted_at.is_none())
+        .ok_or_else(|| comment_not_found(&comment_id))?;
+    if comment.author_id != user.id {
+        return Err(AppError::Forbidden(
+            "Apenas o autor pode editar o comentário".to_string(),
+        ));
+    }
+
+    let now = Utc::now();
+    let mut tx = db.begin().await?;
+    sqlx::query("INSERT INTO comment_revisions (comment_id, body, edited_at) VALUES (?, ?, ?)")
+        .bind(&comment_id)
+        .bind(&comment.body)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("UPDATE comments SET body = ?, updated_at = ? WHERE id = ?")
+        .bind(&input.body)
+        .bind(now)
+        .bind(&comment_id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+
+    let comment = find_comment(db.get_ref(), &task_id, &comment_id)
+        .await?
+        .ok_or_else(|| comment_not_found(&comment_id))?;
+    Ok(HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        message: "Comentário atualizado com sucesso".to_string(),
+        data: Some(comment),
+    }))
+}
+
+// Handler para excluir um comentário.
+// O registro vira um marcador sem texto para preservar as respostas encadeadas.
+#[utoipa::path(
+    tag = "comentários",
+    summary = "Exclui um comentário",
+    responses(
+        (status = 200, body = Message),
+        (status = 403, description = "Apenas o autor pode excluir o comentário", body = Message),
+        (status = 404, body = Message),
+    )
+)]
+#[delete("/tasks/{id}/comments/{comment_id}")]
+pub async fn delete_comment(
+    db: web::Data<SqlitePool>,
+    user: AuthUser,
+    path: web::Path<(String, String)>,
+) -> Result<impl Responder, AppError> {
+    let (task_id, comment_id) = path.into_inner();
+
+    check_task(db.get_ref(), &task_id, &user).await?;
+
+    let comment = find_comment(db.get_ref(), &task_id, &comment_id)
+        .await?
+        .filter(|comment| comment.deleted_at.is_none())
+        .ok_or_else(|| comment_not_found(&comment_id))?;
+    if comment.author_id != user.id {
+        return Err(AppError::Forbidden(
+            "Apenas o autor pode excluir o comentário".to_string(),
+        ));
+    }
+
+    let mut tx = db.begin().await?;
+    sqlx::query("DELETE FROM comment_revisions WHERE comment_id = ?")
+        .bind(&comment_id)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("UPDATE comments SET body = '', deleted_at = ? WHERE id = ?")
+        .bind(Utc::now())
+        .bind(&comment_id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::<()> {
+        success: true,
+        message: format!("Comentário com ID {} excluído com sucesso", comment_id),
+        data: None,
+    }))
+}
+
+// Handler para consultar as versões anteriores de um comentário
+#[utoipa::path(
+    tag = "comentários",
+    summary = "Versões anteriores de um comentário",
+    responses(
+        (status = 200, body = ApiResponse<Vec<CommentRevision>>),
+        (status = 404, body = Message),
+    )
+)]
+#[get("/tasks/{id}/comments/{comment_id}/history")]
+pub async fn get_comment_history(
+    db: web::Data<SqlitePool>,
+    user: AuthUser,
+    path: web::Path<(String, String)>,
+) -> Result<impl Responder, AppError> {
+    let (task_id, comment_id) = path.into_inner();
+
+    check_task(db.get_ref(), &task_id, &user).await?;
+    if find_comment(db.get_ref(), &task_id, &comment_id)
+        .await?
+        .is_none()
+    {
+        return Err(comment_not_found(&comment_id));
+    }
+
+    let revisions = sqlx::query_as::<_, CommentRevision>(
+        "SELECT body, edited_at FROM comment_revisions WHERE comment_id = ? ORDER BY id",
+    )
+    .bind(&comment_id)
+    .fetch_all(db.get_ref())
+    .await?;
+    Ok(HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        message: "Histórico recuperado com sucesso".to_string(),
+        data: Some(revisions),
+    }))
+}
@@ -0,0 +1,339 @@
+use actix_web::{delete, get, post, web, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{
+    sqlite::{SqliteConnection, SqlitePool},
+    FromRow,
+};
+use utoipa::ToSchema;
+
+use crate::auth::AuthUser;
+use crate::error::AppError;
+use crate::openapi::Message;
+use crate::status::TaskStatus;
+use crate::{check_task, find_task, task_not_found, ApiResponse, Task};
+
+// Resumo de uma tarefa no grafo de dependências
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct TaskNode {
+    pub id: String,
+    pub title: String,
+    pub status: TaskStatus,
+    pub priority: i32,
+    pub due_at: Option<DateTime<Utc>>,
+}
+
+// Aresta do grafo: `blocker_id` precisa ser finalizada antes de `blocked_id`
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct Dependency {
+    pub blocker_id: String,
+    pub blocked_id: String,
+    pub created_at: DateTime<Utc>,
+}
+
+// Dados para registrar que uma tarefa bloqueia a tarefa da rota
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct NewDependency {
+    blocker_id: String,
+}
+
+// Grafo de dependências em torno de uma tarefa
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TaskGraph {
+    pub task: TaskNode,
+    // Tarefas das quais ela depende, direta ou indiretamente
+    pub upstream: Vec<TaskNode>,
+    // Tarefas que dependem dela, direta ou indiretamente
+    pub downstream: Vec<TaskNode>,
+    // Arestas entre as tarefas do grafo
+    pub edges: Vec<Dependency>,
+}
+
+// Motivos para recusar uma nova dependência
+enum DependencyRejection {
+    TaskNotFound(String),
+    Duplicate,
+    Cycle,
+}
+
+const SELECT_NODE: &str = "SELECT t.id, t.title, t.status, t.priority, t.due_at FROM tasks t";
+
+// Tarefas alcançáveis a partir de `task_id` seguindo as arestas para trás (bloqueadoras)
+// ou para frente (bloqueadas)
+const UPSTREAM: &str = r#"
+    upstream (id) AS (
+        SELECT ?
+        UNION
+        SELECT d.blocker_id FROM task_dependencies d JOIN upstream u ON d.blocked_id = u.id
+    )
+"#;
+const DOWNSTREAM: &str = r#"
+    downstream (id) AS (
+        SELECT ?
+        UNION
+        SELECT d.blocked_id FROM task_dependencies d JOIN downstream w ON d.blocker_id = w.id
+    )
+"#;
+
+// Bloqueadoras diretas e subtarefas diretas da tarefa que ainda não foram
+// concluídas nem canceladas
+async fn pending_blockers(
+    conn: &mut SqliteConnection,
+    task_id: &str,
+) -> Result<Vec<TaskNode>, sqlx::Error> {
+    sqlx::query_as::<_, TaskNode>(&format!(
+        r#"
+        {}
+        WHERE (t.id IN (SELECT blocker_id FROM task_dependencies WHERE blocked_id = ?)
+               OR t.parent_id = ?)
+          AND t.status NOT IN (?, ?)
+        ORDER BY t.title, t.id
+        "#,
+        SELECT_NODE
+    ))
+    .bind(task_id)
+    .bind(task_id)
+    .bind(TaskStatus::Concluida)
+    .bind(TaskStatus::Cancelada)
+    .fetch_all(conn)
+    .await
+}
+
+// Bloqueadoras e subtarefas que impedem a alteração de `before` para `after`.
+// Só há restrição quando a tarefa passa para Concluída; deve rodar na mesma transação
+// da alteração para que uma bloqueadora reaberta ao mesmo tempo não passe despercebida.
+pub async fn unfinished_blockers(
+    conn: &mut SqliteConnection,
+    before: &Task,
+    after: &Task,
+) -> Result<Vec<TaskNode>, sqlx::Error> {
+    if after.status != TaskStatus::Concluida || before.status == TaskStatus::Concluida {
+        return Ok(Vec::new());
+    }
+    pending_blockers(conn, &after.id).await
+}
+
+// Handler para registrar que `blocker_id` bloqueia a tarefa da rota
+#[utoipa::path(
+    tag = "dependências",
+    summary = "Registra que outra tarefa bloqueia esta",
+    request_body = NewDependency,
+    responses(
+        (status = 201, body = ApiResponse<Dependency>),
+        (status = 404, body = Message),
+        (status = 409, description = "A dependência já existe", body = Message),
+        (status = 422, description = "A dependência criaria um ciclo", body = Message),
+    )
+)]
+#[post("/tasks/{id}/dependencies")]
+pub async fn add_dependency(
+    db: web::Data<SqlitePool>,
+    user: AuthUser,
+    path: web::Path<String>,
+    input: web::Json<NewDependency>,
+) -> Result<impl Responder, AppError> {
+    let task_id = path.into_inner();
+    let blocker_id = input.into_inner().blocker_id;
+
+    if blocker_id == task_id {
+        return Err(AppError::validation(
+            "Uma tarefa não pode bloquear a si mesma",
+        ));
+    }
+
+    let result: Result<Result<Dependency, DependencyRejection>, sqlx::Error> = async {
+        let mut tx = db.begin().await?;
+
+        // A inclusão vem primeiro para obter o bloqueio de escrita: duas dependências
+        // gravadas ao mesmo tempo não conseguem formar um ciclo sem que uma delas perceba
+        let inserted = sqlx::query_as::<_, Dependency>(
+            r#"
+            INSERT INTO task_dependencies (blocker_id, blocked_id, created_at)
+            SELECT b.id, t.id, ?
+            FROM tasks b, tasks t
+            WHERE b.id = ? AND b.owner_id = ? AND t.id = ? AND t.owner_id = ?
+            ON CONFLICT DO NOTHING
+            RETURNING *
+            "#,
+        )
+        .bind(Utc::now())
+        .bind(&blocker_id)
+        .bind(&user.id)
+        .bind(&task_id)
+        .bind(&user.id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(dependency) = inserted else {
+            for id in [&task_id, &blocker_id] {
+                if find_task(&mut *tx, id, &user.id).await?.is_none() {
+                    return Ok(Err(DependencyRejection::TaskNotFound(id.clone())));
+                }
+            }
+            return Ok(Err(DependencyRejection::Duplicate));
+        };
+
+        // Há ciclo se a bloqueadora já depende, direta ou indiretamente, da tarefa
+        let cycle: bool = sqlx::query_scalar(&format!(
+            "WITH RECURSIVE {} SELECT EXISTS (SELECT 1 FROM downstream WHERE id = ?)",
+            DOWNSTREAM
+        ))
+        .bind(&task_id)
+        .bind(&blocker_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if cycle {
+            return Ok(Err(DependencyRejection::Cycle));
+        }
+
+        tx.commit().await?;
+        Ok(Ok(dependency))
+    }
+    .await;
+
+    match result? {
+        Ok(dependency) => Ok(HttpResponse::Created().json(ApiResponse {
+            success: true,
+            message: "Dependência criada com sucesso".to_string(),
+            data: Some(dependency),
+        })),
+        Err(DependencyRejection::TaskNotFound(id)) => Err(task_not_found(&id)),
+        Err(DependencyRejection::Duplicate) => Err(AppError::Conflict(format!(
+            "A tarefa {} já bloqueia a tarefa {}",
+            blocker_id, task_id
+        ))),
+        Err(DependencyRejection::Cycle) => Err(AppError::validation(format!(
+            "A tarefa {} já depende da tarefa {}; a dependência criaria um ciclo",
+            blocker_id, task_id
+        ))),
+    }
+}
+
+// Handler para remover a dependência entre `blocker_id` e a tarefa da rota
+#[utoipa::path(
+    tag = "dependências",
+    summary = "Remove uma dependência",
+    responses(
+        (status = 200, body = Message),
+        (status = 404, body = Message),
+    )
+)]
+#[delete("/tasks/{id}/dependencies/{blocker_id}")]
+pub async fn delete_dependency(
+    db: web::Data<SqlitePool>,
+    user: AuthUser,
+    path: web::Path<(String, String)>,
+) -> Result<impl Responder, AppError> {
+    let (task_id, blocker_id) = path.into_inner();
+
+    check_task(db.get_ref(), &task_id, &user).await?;
+
+    let done = sqlx::query("DELETE FROM task_dependencies WHERE blocker_id = ? AND blocked_id = ?")
+        .bind(&blocker_id)
+        .bind(&task_id)
+        .execute(db.get_ref())
+        .await?;
+    if done.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!(
+            "A tarefa {} não bloqueia a tarefa {}",
+            blocker_id, task_id
+        )));
+    }
+
+    Ok(HttpResponse::Ok().json(ApiResponse::<()> {
+        success: true,
+        message: "Dependência removida com sucesso".to_string(),
+        data: None,
+    }))
+}
+
+async fn fetch_graph(
+    db: &SqlitePool,
+    task_id: &str,
+    owner_id: &str,
+) -> Result<Option<TaskGraph>, sqlx::Error> {
+    let mut conn = db.acquire().await?;
+
+    let Some(task) = sqlx::query_as::<_, TaskNode>(&format!(
+        "{} WHERE t.id = ? AND t.owner_id = ?",
+        SELECT_NODE
+    ))
+    .bind(task_id)
+    .bind(owner_id)
+    .fetch_optional(&mut *conn)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let mut related = Vec::with_capacity(2);
+    for (cte, name) in [(UPSTREAM, "upstream"), (DOWNSTREAM, "downstream")] {
+        let nodes = sqlx::query_as::<_, TaskNode>(&format!(
+            r#"
+            WITH RECURSIVE {}
+            {} JOIN {} r ON r.id = t.id
+            WHERE t.id != ? AND t.owner_id = ?
+            ORDER BY t.due_at IS NULL, t.due_at, t.title, t.id
+            "#,
+            cte, SELECT_NODE, name
+        ))
+        .bind(task_id)
+        .bind(task_id)
+        .bind(owner_id)
+        .fetch_all(&mut *conn)
+        .await?;
+        related.push(nodes);
+    }
+    let downstream = related.pop().unwrap_or_default();
+    let upstream = related.pop().unwrap_or_default();
+
+    let edges = sqlx::query_as::<_, Dependency>(&format!(
+        r#"
+        WITH RECURSIVE {}, {},
+        nodes (id) AS (SELECT id FROM upstream UNION SELECT id FROM downstream)
+        SELECT d.* FROM task_dependencies d
+        WHERE d.blocker_id IN nodes AND d.blocked_id IN nodes
+        ORDER BY d.created_at, d.blocker_id, d.blocked_id
+        "#,
+        UPSTREAM, DOWNSTREAM
+    ))
+    .bind(task_id)
+    .bind(task_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(Some(TaskGraph {
+        task,
+        upstream,
+        downstream,
+        edges,
+    }))
+}
+
+// Handler para consultar o grafo de dependências de uma tarefa, usado para
+// desenhar o caminho crítico até ela e o impacto de um atraso nas seguintes
+#[utoipa::path(
+    tag = "dependências",
+    summary = "Grafo de dependências em torno de uma tarefa",
+    responses(
+        (status = 200, body = ApiResponse<TaskGraph>),
+        (status = 404, body = Message),
+    )
+)]
+#[get("/tasks/{id}/graph")]
+pub async fn get_graph(
+    db: web::Data<SqlitePool>,
+    user: AuthUser,
+    path: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    let task_id = path.into_inner();
+
+    let graph = fetch_graph(db.get_ref(), &task_id, &user.id)
+        .await?
+        .ok_or_else(|| task_not_found(&task_id))?;
+    Ok(HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        message: "Grafo de dependências recuperado com sucesso".to_string(),
+        data: Some(graph),
+    }))
+}
@@ -0,0 +1,172 @@
+use actix_web::{
+    http::{header, StatusCode},
+    HttpResponse, ResponseError,
+};
+use serde::Serialize;
+use std::fmt;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::dependencies::TaskNode;
+use crate::etag::etag;
+use crate::Task;
+
+// Campo recusado pela validação, com o motivo
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+// Erros das rotas da API. Cada variante vira uma resposta no formato ApiResponse com
+// `success: false` e um código estável em `code`; os detalhes dos erros internos vão
+// apenas para o log.
+#[derive(Debug)]
+pub enum AppError {
+    // Requisição malformada ou parâmetros inválidos (400)
+    BadRequest(String),
+    // Token ausente, inválido ou expirado (401)
+    Unauthorized(String),
+    // Ação reservada a outro usuário, como editar o comentário de alguém (403)
+    Forbidden(String),
+    // Recurso inexistente ou de outro usuário (404)
+    NotFound(String),
+    // Recurso duplicado ou em uso (409)
+    Conflict(String),
+    // A tarefa iria para Concluída com bloqueadoras ou subtarefas em aberto (409)
+    Blocked(Vec<TaskNode>),
+    // O If-Match não corresponde à versão atual; a resposta traz a cópia atual, se houver (412)
+    PreconditionFailed {
+        message: String,
+        current: Option<Box<Task>>,
+    },
+    // Arquivo acima do tamanho permitido (413)
+    PayloadTooLarge(String),
+    // Tipo de arquivo não aceito (415)
+    UnsupportedMediaType(String),
+    // Dados recusados pela validação, com os campos quando conhecidos (422)
+    Validation(String, Vec<FieldError>),
+    // Falha no banco de dados (500)
+    Database(sqlx::Error),
+    // Outras falhas internas (500)
+    Internal(String),
+}
+
+impl AppError {
+    // Erro de validação sem campos associados
+    pub fn validation(message: impl Into<String>) -> Self {
+        AppError::Validation(message.into(), Vec::new())
+    }
+
+    // Código estável enviado ao cliente; não muda com o texto da mensagem
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::BadRequest(_) => "bad_request",
+            AppError::Unauthorized(_) => "unauthorized",
+            AppError::Forbidden(_) => "forbidden",
+            AppError::NotFound(_) => "not_found",
+            AppError::Conflict(_) => "conflict",
+            AppError::Blocked(_) => "blocked",
+            AppError::PreconditionFailed { .. } => "precondition_failed",
+            AppError::PayloadTooLarge(_) => "payload_too_large",
+            AppError::UnsupportedMediaType(_) => "unsupported_media_type",
+            AppError::Validation(..) => "validation_failed",
+            AppError::Database(_) | AppError::Internal(_) => "internal_error",
+        }
+    }
+
+    // Mensagem enviada ao cliente; erros internos não expõem detalhes
+    fn message(&self) -> String {
+        match self {
+            AppError::BadRequest(message)
+            | AppError::Unauthorized(message)
+            | AppError::Forbidden(message)
+            | AppError::NotFound(message)
+            | AppError::Conflict(message)
+            | AppError::PreconditionFailed { message, .. }
+            | AppError::PayloadTooLarge(message)
+            | AppError::UnsupportedMediaType(message)
+            | AppError::Validation(message, _) => message.clone(),
+            AppError::Blocked(blockers) => format!(
+                "A tarefa depende de {} tarefa(s) não finalizada(s)",
+                blockers.len()
+            ),
+            AppError::Database(_) | AppError::Internal(_) => "Erro interno do servidor".to_string(),
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::Database(e) => write!(f, "Erro no banco de dados: {}", e),
+            AppError::Internal(detail) => f.write_str(detail),
+            _ => f.write_str(&self.message()),
+        }
+    }
+}
+
+impl From<sqlx::Error> for AppError {
+    fn from(e: sqlx::Error) -> Self {
+        AppError::Database(e)
+    }
+}
+
+// Corpo das respostas de erro: o envelope ApiResponse com o código e os campos recusados
+#[derive(Serialize)]
+struct ErrorBody<'a> {
+    success: bool,
+    message: String,
+    code: &'static str,
+    #[serde(skip_serializing_if = "<[FieldError]>::is_empty")]
+    errors: &'a [FieldError],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<serde_json::Value>,
+}
+
+impl ResponseError for AppError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            AppError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            AppError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            AppError::Forbidden(_) => StatusCode::FORBIDDEN,
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::Conflict(_) | AppError::Blocked(_) => StatusCode::CONFLICT,
+            AppError::PreconditionFailed { .. } => StatusCode::PRECONDITION_FAILED,
+            AppError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            AppError::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            AppError::Validation(..) => StatusCode::UNPROCESSABLE_ENTITY,
+            AppError::Database(_) | AppError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        if let AppError::Database(_) | AppError::Internal(_) = self {
+            log::error!("{}", self);
+        }
+
+        let mut response = HttpResponse::build(self.status_code());
+        let data = match self {
+            AppError::Blocked(blockers) => serde_json::to_value(blockers).ok(),
+            AppError::PreconditionFailed {
+                current: Some(task),
+                ..
+            } => {
+                response.insert_header((header::ETAG, etag(task)));
+                serde_json::to_value(task).ok()
+            }
+            _ => None,
+        };
+        let errors = match self {
+            AppError::Validation(_, errors) => errors.as_slice(),
+            _ => &[],
+        };
+        response.json(ErrorBody {
+            success: false,
+            message: self.message(),
+            code: self.code(),
+            errors,
+            data,
+        })
+    }
+}
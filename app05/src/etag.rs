@@ -0,0 +1,102 @@
+use actix_web::{http::header, HttpRequest};
+
+use crate::Task;
+
+// ETag da tarefa, derivada da coluna version
+pub fn etag(task: &Task) -> String {
+    format!("\"{}\"", task.version)
+}
+
+// Condição enviada no cabeçalho If-Match
+pub enum IfMatch {
+    Any,
+    Versions(Vec<i64>),
+}
+
+impl IfMatch {
+    // Lê o cabeçalho If-Match; ETags que não correspondem a uma versão são ignoradas
+    pub fn from_request(req: &HttpRequest) -> Option<IfMatch> {
+        let raw = req.headers().get(header::IF_MATCH)?.to_str().ok()?.trim();
+        if raw == "*" {
+            return Some(IfMatch::Any);
+        }
+
+        let versions = raw
+            .split(',')
+            .filter_map(|tag| {
+                tag.trim()
+                    .trim_start_matches("W/")
+                    .trim_matches('"')
+                    .parse::<i64>()
+                    .ok()
+            })
+            .collect();
+        Some(IfMatch::Versions(versions))
+    }
+
+    pub fn matches(&self, version: i64) -> bool {
+        match self {
+            IfMatch::Any => true,
+            IfMatch::Versions(versions) => versions.contains(&version),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::test::TestRequest;
+
+    use super::*;
+
+    fn if_match(value: &str) -> Option<IfMatch> {
+        let req = TestRequest::default()
+            .insert_header((header::IF_MATCH, value))
+            .to_http_request();
+        IfMatch::from_request(&req)
+    }
+
+    #[test]
+    fn missing_header_has_no_condition() {
+        let req = TestRequest::default().to_http_request();
+        assert!(IfMatch::from_request(&req).is_none());
+    }
+
+    #[test]
+    fn quoted_version_matches_only_itself() {
+        let condition = if_match("\"3\"").unwrap();
+        assert!(condition.matches(3));
+        assert!(!condition.matches(4));
+    }
+
+    #[test]
+    fn weak_etag_is_compared_by_version() {
+        let condition = if_match("W/\"7\"").unwrap();
+        assert!(condition.matches(7));
+        assert!(!condition.matches(1));
+    }
+
+    #[test]
+    fn star_matches_any_version() {
+        let condition = if_match(" * ").unwrap();
+        assert!(matches!(condition, IfMatch::Any));
+        assert!(condition.matches(1));
+        assert!(condition.matches(42));
+    }
+
+    #[test]
+    fn comma_separated_list_matches_each_version() {
+        let condition = if_match("\"1\", W/\"2\" ,\"5\"").unwrap();
+        for version in [1, 2, 5] {
+            assert!(condition.matches(version), "versão {}", version);
+        }
+        assert!(!condition.matches(3));
+    }
+
+    #[test]
+    fn unknown_etags_match_nothing() {
+        // Um If-Match presente mas sem versões válidas não pode liberar a gravação
+        let condition = if_match("\"abc\", W/\"\"").unwrap();
+        assert!(!condition.matches(0));
+        assert!(!condition.matches(1));
+    }
+}
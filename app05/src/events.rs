@@ -0,0 +1,186 @@
+use actix_web::{get, web, HttpRequest, HttpResponse, Responder};
+use futures_util::{stream, StreamExt};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use utoipa::ToSchema;
+
+use crate::auth::AuthUser;
+use crate::Task;
+
+// Quantidade de eventos mantidos em memória para reenvio após reconexão
+const HISTORY_SIZE: usize = 1000;
+// Intervalo entre comentários de keep-alive enviados ao cliente
+const KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(rename_all = "lowercase")]
+pub enum TaskEventKind {
+    Created,
+    Updated,
+    Deleted,
+    // Lembrete da tarefa disparado pelo agendador
+    Reminder,
+}
+
+impl TaskEventKind {
+    fn name(self) -> &'static str {
+        match self {
+            TaskEventKind::Created => "created",
+            TaskEventKind::Updated => "updated",
+            TaskEventKind::Deleted => "deleted",
+            TaskEventKind::Reminder => "reminder",
+        }
+    }
+}
+
+// Evento enviado aos clientes conectados em /tasks/events
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskEvent {
+    pub id: u64,
+    pub kind: TaskEventKind,
+    pub task: Task,
+}
+
+impl TaskEvent {
+    // Formata o evento no padrão Server-Sent Events
+    fn to_sse(&self) -> String {
+        format!(
+            "id: {}\nevent: {}\ndata: {}\n\n",
+            self.id,
+            self.kind.name(),
+            serde_json::to_string(&self.task).unwrap_or_default()
+        )
+    }
+}
+
+struct History {
+    last_id: u64,
+    events: VecDeque<TaskEvent>,
+}
+
+// Distribui as alterações de tarefas para os clientes conectados
+pub struct TaskEvents {
+    history: Mutex<History>,
+    sender: broadcast::Sender<TaskEvent>,
+}
+
+impl TaskEvents {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(HISTORY_SIZE);
+        TaskEvents {
+            history: Mutex::new(History {
+                last_id: 0,
+                events: VecDeque::with_capacity(HISTORY_SIZE),
+            }),
+            sender,
+        }
+    }
+
+    // Registra o evento no histórico e envia para os assinantes
+    pub fn publish(&self, kind: TaskEventKind, task: Task) {
+        let mut history = self.history.lock().unwrap();
+        history.last_id += 1;
+        let event = TaskEvent {
+            id: history.last_id,
+            kind,
+            task,
+        };
+
+        if history.events.len() == HISTORY_SIZE {
+            history.events.pop_front();
+        }
+        history.events.push_back(event.clone());
+
+        // Não há problema se ninguém estiver ouvindo
+        let _ = self.sender.send(event);
+    }
+
+    // Retorna os eventos posteriores a `last_id` e um receptor para os próximos.
+    // O lock garante que nenhum evento se perca entre o histórico e o receptor.
+    fn subscribe(
+        &self,
+        owner_id: &str,
+        last_id: Option<u64>,
+    ) -> (Vec<TaskEvent>, broadcast::Receiver<TaskEvent>) {
+        let history = self.history.lock().unwrap();
+        let receiver = self.sender.subscribe();
+        let replay = match last_id {
+            Some(last_id) => history
+                .events
+                .iter()
+                .filter(|event| event.id > last_id && event.task.owner_id == owner_id)
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        };
+        (replay, receiver)
+    }
+}
+
+impl Default for TaskEvents {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Handler para acompanhar as alterações de tarefas em tempo real (Server-Sent Events).
+// Clientes que reconectam com o cabeçalho Last-Event-ID recebem os eventos perdidos.
+// Cada usuário recebe apenas os eventos das próprias tarefas.
+#[utoipa::path(
+    tag = "eventos",
+    summary = "Stream Server-Sent Events com as alterações das tarefas",
+    params(
+        ("Last-Event-ID" = Option<u64>, Header, description = "Último evento recebido antes da reconexão"),
+    ),
+    responses(
+        (status = 200, description = "Eventos created, updated, deleted e reminder com a tarefa em data", content_type = "text/event-stream", body = String),
+    )
+)]
+#[get("/tasks/events")]
+pub async fn task_events(
+    req: HttpRequest,
+    events: web::Data<TaskEvents>,
+    user: AuthUser,
+) -> impl Responder {
+    let last_id = req
+        .headers()
+        .get("Last-Event-ID")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok());
+
+    let (replay, receiver) = events.subscribe(&user.id, last_id);
+
+    // Primeiro reenvia o histórico, depois segue o canal de broadcast
+    let replay = stream::iter(
+        replay
+            .into_iter()
+            .map(|event| Ok::<_, actix_web::Error>(web::Bytes::from(event.to_sse()))),
+    );
+    let live = stream::unfold((receiver, user.id), |(mut receiver, owner_id)| async move {
+        loop {
+            let item = match tokio::time::timeout(KEEP_ALIVE, receiver.recv()).await {
+                Ok(Ok(event)) if event.task.owner_id != owner_id => continue,
+                Ok(Ok(event)) => web::Bytes::from(event.to_sse()),
+                Ok(Err(broadcast::error::RecvError::Lagged(skipped))) => {
+                    // O cliente ficou para trás: encerra a conexão para que ele
+                    // reconecte com Last-Event-ID e recupere o histórico
+                    log::warn!("Cliente de eventos atrasado, {} eventos perdidos", skipped);
+                    return None;
+                }
+                Ok(Err(broadcast::error::RecvError::Closed)) => return None,
+                Err(_) => web::Bytes::from_static(b": keep-alive\n\n"),
+            };
+            return Some((Ok(item), (receiver, owner_id)));
+        }
+    });
+
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(("Cache-Control", "no-cache"))
+        .insert_header(("X-Accel-Buffering", "no"))
+        .streaming(replay.chain(live))
+}
@@ -0,0 +1,297 @@
+use actix_web::{
+    guard,
+    http::{header, Method},
+    mime, web, HttpRequest, HttpResponse,
+};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::env;
+use std::path::PathBuf;
+
+use crate::error::AppError;
+
+// Página carregada pelo navegador em qualquer rota do cliente
+const INDEX: &str = "index.html";
+// Arquivos gerados pelo build com hash no nome; o conteúdo de uma URL nunca muda
+const HASHED_PREFIX: &str = "static/";
+// Versões pré-comprimidas procuradas ao lado de cada arquivo, em ordem de preferência
+const ENCODINGS: [(&str, &str); 2] = [("br", "br"), ("gzip", "gz")];
+
+// Frontend compilado embutido no binário (cargo build --features embed-frontend)
+#[cfg(feature = "embed-frontend")]
+#[derive(rust_embed::RustEmbed)]
+#[folder = "frontend/build/"]
+struct Embedded;
+
+// Origem dos arquivos do frontend
+enum Source {
+    Dir(PathBuf),
+    #[cfg(feature = "embed-frontend")]
+    Embedded,
+}
+
+impl Source {
+    async fn read(&self, path: &str) -> Option<Cow<'static, [u8]>> {
+        match self {
+            Source::Dir(dir) => tokio::fs::read(dir.join(path)).await.ok().map(Cow::Owned),
+            #[cfg(feature = "embed-frontend")]
+            Source::Embedded => Embedded::get(path).map(|file| file.data),
+        }
+    }
+}
+
+// Frontend React servido pelo próprio servidor. FRONTEND_DIR aponta para o diretório do
+// build; sem ele, usa os arquivos embutidos ou, se não houver, serve apenas a API.
+pub struct Frontend {
+    source: Option<Source>,
+}
+
+impl Frontend {
+    pub async fn from_env() -> Self {
+        let source = match env::var("FRONTEND_DIR") {
+            Ok(dir) => Some(Source::Dir(PathBuf::from(dir))),
+            #[cfg(feature = "embed-frontend")]
+            Err(_) => Some(Source::Embedded),
+            #[cfg(not(feature = "embed-frontend"))]
+            Err(_) => None,
+        };
+
+        // Sem index.html não há o que servir; a API continua funcionando normalmente
+        let source = match source {
+            Some(source) if source.read(INDEX).await.is_none() => {
+                log::warn!("Frontend sem {}; servindo apenas a API", INDEX);
+                None
+            }
+            source => source,
+        };
+        Frontend { source }
+    }
+
+    // Registra as rotas do frontend. Deve ser chamada antes das rotas da API: navegações
+    // do navegador (Accept: text/html) recebem a página mesmo em caminhos da API, como
+    // /tasks, e as demais requisições seguem para a API.
+    pub fn configure(&self, cfg: &mut web::ServiceConfig) {
+        if self.source.is_none() {
+            return;
+        }
+        cfg.service(
+            web::resource("/{path:.*}")
+                .guard(guard::Any(guard::Get()).or(guard::Head()))
+                .guard(guard::Acceptable::new(mime::TEXT_HTML))
+                .to(serve),
+        )
+        .default_service(web::to(serve));
+    }
+
+    // Responde com o arquivo, preferindo a versão pré-comprimida aceita pelo cliente
+    async fn respond(&self, req: &HttpRequest, path: &str) -> Option<HttpResponse> {
+        let source = self.source.as_ref()?;
+        let mut found = None;
+        for (encoding, extension) in accepted_encodings(req) {
+            if let Some(data) = source.read(&format!("{}.{}", path, extension)).await {
+                found = Some((Some(encoding), data));
+                break;
+            }
+        }
+        let (encoding, data) = match found {
+            Some(found) => found,
+            None => (None, source.read(path).await?),
+        };
+
+        let cache_control = if path == INDEX {
+            "no-cache"
+        } else if path.starts_with(HASHED_PREFIX) {
+            "public, max-age=31536000, immutable"
+        } else {
+            "public, max-age=3600"
+        };
+        // A ETag muda com a codificação, já que o corpo enviado é outro
+        let etag = format!("\"{}\"", &hex::encode(Sha256::digest(&data))[..32]);
+
+        // O index.html também é a resposta a navegações em caminhos da API
+        let vary = if path == INDEX {
+            "Accept, Accept-Encoding"
+        } else {
+            "Accept-Encoding"
+        };
+
+        let not_modified = if_none_match(req, &etag);
+        let mut response = if not_modified {
+            HttpResponse::NotModified()
+        } else {
+            HttpResponse::Ok()
+        };
+        response
+            .insert_header((header::CACHE_CONTROL, cache_control))
+            .insert_header((header::ETAG, etag))
+            .insert_header((header::VARY, vary));
+        if not_modified {
+            return Some(response.finish());
+        }
+
+        if let Some(encoding) = encoding {
+            response.insert_header((header::CONTENT_ENCODING, encoding));
+        }
+        Some(
+            response
+                .content_type(mime_guess::from_path(path).first_or_octet_stream())
+                .body(data.into_owned()),
+        )
+    }
+}
+
+// Caminho relativo do arquivo pedido; recusa segmentos vazios, ocultos ou que sobem de nível
+fn asset_path(path: &str) -> Option<String> {
+    let path = path.trim_start_matches('/');
+    if path.is_empty() {
+        return Some(INDEX.to_string());
+    }
+    let valid = path
+        .split('/')
+        .all(|segment| !segment.is_empty() && !segment.starts_with('.') && !segment.contains('\\'));
+    valid.then(|| path.to_string())
+}
+
+// Codificações aceitas pelo cliente no cabeçalho Accept-Encoding (q=0 recusa)
+fn accepted_encodings(req: &HttpRequest) -> Vec<(&'static str, &'static str)> {
+    let accepted: Vec<&str> = req
+        .headers()
+        .get(header::ACCEPT_ENCODING)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("")
+        .split(',')
+        .filter_map(|item| {
+            let mut parts = item.split(';').map(str::trim);
+            let name = parts.next()?;
+            let refused = parts.any(|param| {
+                param.strip_prefix("q=").and_then(|q| q.parse::<f32>().ok()) == Some(0.0)
+            });
+            (!refused).then_some(name)
+        })
+        .collect();
+
+    ENCODINGS
+        .into_iter()
+        .filter(|(encoding, _)| {
+            accepted
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(encoding))
+        })
+        .collect()
+}
+
+fn if_none_match(req: &HttpRequest, etag: &str) -> bool {
+    req.headers()
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| {
+            value
+                .split(',')
+                .any(|tag| tag.trim() == "*" || tag.trim().trim_start_matches("W/") == etag)
+        })
+}
+
+fn accepts_html(req: &HttpRequest) -> bool {
+    req.headers()
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.contains("text/html"))
+}
+
+fn not_found() -> AppError {
+    AppError::NotFound("Rota não encontrada".to_string())
+}
+
+// Serve os arquivos do build; navegações para rotas do cliente recebem o index.html
+async fn serve(req: HttpRequest, frontend: web::Data<Frontend>) -> Result<HttpResponse, AppError> {
+    if req.method() != Method::GET && req.method() != Method::HEAD {
+        return Err(not_found());
+    }
+
+    if let Some(path) = asset_path(req.path()) {
+        if let Some(response) = frontend.respond(&req, &path).await {
+            return Ok(response);
+        }
+    }
+
+    if accepts_html(&req) {
+        if let Some(response) = frontend.respond(&req, INDEX).await {
+            return Ok(response);
+        }
+    }
+    Err(not_found())
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::test::TestRequest;
+
+    use super::*;
+
+    fn request(name: header::HeaderName, value: &str) -> HttpRequest {
+        TestRequest::default()
+            .insert_header((name, value))
+            .to_http_request()
+    }
+
+    #[test]
+    fn asset_path_keeps_plain_paths() {
+        assert_eq!(asset_path("/").as_deref(), Some(INDEX));
+        assert_eq!(
+            asset_path("/static/js/main.3f2a.js").as_deref(),
+            Some("static/js/main.3f2a.js")
+        );
+    }
+
+    #[test]
+    fn asset_path_rejects_traversal_dotfiles_and_backslashes() {
+        for path in [
+            "/../Cargo.toml",
+            "/static/../../.env",
+            "/.env",
+            "/static/.git/config",
+            "/static\\..\\main.rs",
+            "/static//main.js",
+        ] {
+            assert_eq!(asset_path(path), None, "{}", path);
+        }
+    }
+
+    #[test]
+    fn encodings_follow_preference_and_refusals() {
+        let req = request(header::ACCEPT_ENCODING, "gzip, deflate, br");
+        assert_eq!(accepted_encodings(&req), ENCODINGS.to_vec());
+
+        let req = request(header::ACCEPT_ENCODING, "br;q=0, gzip;q=0.5");
+        assert_eq!(accepted_encodings(&req), vec![("gzip", "gz")]);
+
+        let req = request(header::ACCEPT_ENCODING, "BR");
+        assert_eq!(accepted_encodings(&req), vec![("br", "br")]);
+
+        let req = TestRequest::default().to_http_request();
+        assert!(accepted_encodings(&req).is_empty());
+    }
+
+    #[test]
+    fn if_none_match_accepts_weak_and_listed_etags() {
+        let etag = "\"abc123\"";
+        assert!(if_none_match(&request(header::IF_NONE_MATCH, etag), etag));
+        assert!(if_none_match(
+            &request(header::IF_NONE_MATCH, "W/\"abc123\""),
+            etag
+        ));
+        assert!(if_none_match(
+            &request(header::IF_NONE_MATCH, "\"old\", W/\"abc123\""),
+            etag
+        ));
+        assert!(if_none_match(&request(header::IF_NONE_MATCH, "*"), etag));
+        assert!(!if_none_match(
+            &request(header::IF_NONE_MATCH, "\"old\""),
+            etag
+        ));
+        assert!(!if_none_match(
+            &TestRequest::default().to_http_request(),
+            etag
+        ));
+    }
+}
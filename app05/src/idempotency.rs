@@ -0,0 +1,251 @@
+use actix_web::{
+    http::{header, StatusCode},
+    HttpRequest, HttpResponse,
+};
+use chrono::{Duration, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::{
+    sqlite::{SqliteConnection, SqlitePool},
+    FromRow,
+};
+use std::env;
+
+use crate::error::AppError;
+
+// Cabeçalho com a chave escolhida pelo cliente
+const IDEMPOTENCY_KEY: &str = "Idempotency-Key";
+// Cabeçalho que marca a resposta como repetição de uma requisição anterior
+const IDEMPOTENT_REPLAYED: &str = "Idempotent-Replayed";
+// Tamanho máximo da chave
+const MAX_KEY_LENGTH: usize = 255;
+
+// Por quanto tempo a resposta fica guardada, lido do arquivo .env
+pub struct IdempotencyConfig {
+    ttl: Duration,
+}
+
+impl IdempotencyConfig {
+    pub fn from_env() -> Self {
+        let hours = env::var("IDEMPOTENCY_KEY_TTL_HOURS")
+            .unwrap_or_else(|_| "24".to_string())
+            .parse::<i64>()
+            .expect("IDEMPOTENCY_KEY_TTL_HOURS deve ser um número");
+
+        IdempotencyConfig {
+            ttl: Duration::hours(hours),
+        }
+    }
+}
+
+// Lê o cabeçalho Idempotency-Key. A chave deve ter de 1 a 255 caracteres ASCII visíveis;
+// fora disso a requisição é recusada com 400.
+pub fn key_from_request(req: &HttpRequest) -> Result<Option<String>, AppError> {
+    let Some(value) = req.headers().get(IDEMPOTENCY_KEY) else {
+        return Ok(None);
+    };
+    match value.to_str() {
+        Ok(key)
+            if !key.is_empty()
+                && key.len() <= MAX_KEY_LENGTH
+                && key.chars().all(|c| c.is_ascii_graphic()) =>
+        {
+            Ok(Some(key.to_string()))
+        }
+        _ => Err(AppError::BadRequest(format!(
+            "{} deve ter de 1 a {} caracteres ASCII visíveis",
+            IDEMPOTENCY_KEY, MAX_KEY_LENGTH
+        ))),
+    }
+}
+
+// Hash SHA-256 do corpo da requisição, usado para detectar a reutilização da chave
+pub fn fingerprint<T: Serialize>(value: &T) -> String {
+    let json = serde_json::to_vec(value).unwrap_or_default();
+    hex::encode(Sha256::digest(json))
+}
+
+// Resposta guardada para uma chave
+#[derive(Debug, FromRow)]
+pub struct StoredResponse {
+    request_hash: String,
+    status: i64,
+    etag: Option<String>,
+    body: String,
+}
+
+impl StoredResponse {
+    pub fn new(
+        request_hash: String,
+        status: StatusCode,
+        etag: Option<String>,
+        body: String,
+    ) -> Self {
+        StoredResponse {
+            request_hash,
+            status: i64::from(status.as_u16()),
+            etag,
+            body,
+        }
+    }
+
+    // Repete a resposta original; com um corpo diferente, a chave é recusada com 422
+    pub fn replay(self, request_hash: &str, key: &str) -> Result<HttpResponse, AppError> {
+        if self.request_hash != request_hash {
+            return Err(AppError::validation(format!(
+                "A chave de idempotência {} já foi usada com outro corpo de requisição",
+                key
+            )));
+        }
+
+        let status = u16::try_from(self.status)
+            .ok()
+            .and_then(|status| StatusCode::from_u16(status).ok())
+            .unwrap_or(StatusCode::OK);
+        let mut response = HttpResponse::build(status);
+        response
+            .content_type("application/json")
+            .insert_header((IDEMPOTENT_REPLAYED, "true"));
+        if let Some(etag) = self.etag {
+            response.insert_header((header::ETAG, etag));
+        }
+        Ok(response.body(self.body))
+    }
+}
+
+// Busca a resposta guardada para a chave, se ainda não expirou
+pub async fn lookup(
+    db: &SqlitePool,
+    owner_id: &str,
+    key: &str,
+) -> Result<Option<StoredResponse>, sqlx::Error> {
+    sqlx::query_as::<_, StoredResponse>(
+        r#"
+        SELECT request_hash, status, etag, body FROM idempotency_keys
+        WHERE owner_id = ? AND key = ? AND expires_at > ?
+        "#,
+    )
+    .bind(owner_id)
+    .bind(key)
+    .bind(Utc::now())
+    .fetch_optional(db)
+    .await
+}
+
+// Guarda a resposta na mesma transação que criou o recurso e remove as chaves expiradas.
+// Retorna false se outra requisição com a mesma chave gravou primeiro; nesse caso a
+// transação deve ser descartada e a resposta guardada, repetida.
+pub async fn store(
+    conn: &mut SqliteConnection,
+    config: &IdempotencyConfig,
+    owner_id: &str,
+    key: &str,
+    response: &StoredResponse,
+) -> Result<bool, sqlx::Error> {
+    let now = Utc::now();
+    sqlx::query("DELETE FROM idempotency_keys WHERE expires_at <= ?")
+        .bind(now)
+        .execute(&mut *conn)
+        .await?;
+
+    let stored = sqlx::query(
+        r#"
+        INSERT INTO idempotency_keys (owner_id, key, request_hash, status, etag, body, created_at, expires_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT (owner_id, key) DO NOTHING
+        "#,
+    )
+    .bind(owner_id)
+    .bind(key)
+    .bind(&response.request_hash)
+    .bind(response.status)
+    .bind(&response.etag)
+    .bind(&response.body)
+    .bind(now)
+    .bind(now + config.ttl)
+    .execute(&mut *conn)
+    .await?;
+    Ok(stored.rows_affected() == 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::test::TestRequest;
+    use serde_json::json;
+
+    use super::*;
+
+    fn key(value: &str) -> Result<Option<String>, AppError> {
+        let req = TestRequest::default()
+            .insert_header((IDEMPOTENCY_KEY, value))
+            .to_http_request();
+        key_from_request(&req)
+    }
+
+    #[test]
+    fn equal_payloads_have_equal_fingerprints() {
+        let first = json!({"title": "Relatório", "priority": 2});
+        let second = json!({"title": "Relatório", "priority": 2});
+        assert_eq!(fingerprint(&first), fingerprint(&second));
+        assert_eq!(fingerprint(&first).len(), 64);
+    }
+
+    #[test]
+    fn changed_field_changes_the_fingerprint() {
+        let original = json!({"title": "Relatório", "priority": 2});
+        let changed = json!({"title": "Relatório", "priority": 3});
+        assert_ne!(fingerprint(&original), fingerprint(&changed));
+    }
+
+    #[test]
+    fn valid_keys_are_accepted() {
+        let req = TestRequest::default().to_http_request();
+        assert!(matches!(key_from_request(&req), Ok(None)));
+        assert_eq!(key("pedido-42").unwrap().as_deref(), Some("pedido-42"));
+        let longest = "k".repeat(MAX_KEY_LENGTH);
+        assert_eq!(key(&longest).unwrap(), Some(longest));
+    }
+
+    #[test]
+    fn long_or_invalid_keys_are_rejected() {
+        let too_long = "k".repeat(MAX_KEY_LENGTH + 1);
+        for invalid in ["", "com espaço", "tab\tinterno", too_long.as_str()] {
+            assert!(
+                matches!(key(invalid), Err(AppError::BadRequest(_))),
+                "{:?}",
+                invalid
+            );
+        }
+        // Bytes fora do ASCII não são texto válido no cabeçalho
+        let req = TestRequest::default()
+            .insert_header((
+                IDEMPOTENCY_KEY,
+                header::HeaderValue::from_bytes("chave-ç".as_bytes()).unwrap(),
+            ))
+            .to_http_request();
+        assert!(matches!(
+            key_from_request(&req),
+            Err(AppError::BadRequest(_))
+        ));
+    }
+
+    #[test]
+    fn replay_requires_the_same_payload() {
+        let stored = || {
+            StoredResponse::new(
+                "hash".to_string(),
+                StatusCode::CREATED,
+                Some("\"1\"".to_string()),
+                "{}".to_string(),
+            )
+        };
+        let response = stored().replay("hash", "pedido-42").unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(response.headers().get(IDEMPOTENT_REPLAYED).unwrap(), "true");
+        assert_eq!(response.headers().get(header::ETAG).unwrap(), "\"1\"");
+        assert!(matches!(
+            stored().replay("outro", "pedido-42"),
+            Err(AppError::Validation(..))
+        ));
+    }
+}
@@ -0,0 +1,392 @@
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{sqlite::SqlitePool, FromRow, Sqlite, Transaction};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::audit;
+use crate::auth::AuthUser;
+use crate::error::AppError;
+use crate::events::{TaskEventKind, TaskEvents};
+use crate::openapi::Message;
+use crate::{find_task_details, task_not_found, ApiResponse, Task};
+
+// Tamanho máximo do nome de uma etiqueta
+const MAX_NAME_LENGTH: usize = 50;
+// Cor usada quando a etiqueta é criada sem cor
+const DEFAULT_COLOR: &str = "#9e9e9e";
+
+// Etiquetas da tarefa como um array JSON de {id, name, color}, em ordem alfabética.
+// Usada nas listagens de `tasks`.
+pub const LABELS: &str = r#"
+    (SELECT json_group_array(json_object('id', l.id, 'name', l.name, 'color', l.color))
+     FROM (SELECT l.* FROM task_labels tl JOIN labels l ON l.id = tl.label_id
+           WHERE tl.task_id = tasks.id ORDER BY l.name) l) AS labels
+"#;
+
+// Modelo de etiqueta
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct Label {
+    pub id: String,
+    pub owner_id: String,
+    pub name: String,
+    pub color: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Etiqueta como aparece dentro da tarefa
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+pub struct TaskLabel {
+    pub id: String,
+    pub name: String,
+    pub color: String,
+}
+
+// Dados para criar ou editar uma etiqueta
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct LabelInput {
+    name: String,
+    // Cor no formato #rrggbb
+    color: Option<String>,
+}
+
+impl LabelInput {
+    // Valida e normaliza nome e cor; a cor ausente vira `default_color`
+    fn validate(self, default_color: &str) -> Result<(String, String), String> {
+        let name = self.name.trim().to_string();
+        if name.is_empty() || name.chars().count() > MAX_NAME_LENGTH {
+            return Err(format!(
+                "O nome da etiqueta deve ter entre 1 e {} caracteres",
+                MAX_NAME_LENGTH
+            ));
+        }
+        if name.contains(',') {
+            return Err("O nome da etiqueta não pode conter vírgulas".to_string());
+        }
+
+        let color = self
+            .color
+            .map(|color| color.trim().to_ascii_lowercase())
+            .unwrap_or_else(|| default_color.to_string());
+        let valid = color.len() == 7
+            && color.starts_with('#')
+            && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+        if !valid {
+            return Err(format!("Cor inválida: {} (use o formato #rrggbb)", color));
+        }
+        Ok((name, color))
+    }
+}
+
+fn label_not_found(id: &str) -> AppError {
+    AppError::NotFound(format!("Etiqueta com ID {} não encontrada", id))
+}
+
+// Nome repetido vira 409; as demais falhas do banco seguem como erro interno
+fn save_error(e: sqlx::Error, name: &str) -> AppError {
+    let unique_violation = e
+        .as_database_error()
+        .map(|db_err| db_err.is_unique_violation())
+        .unwrap_or(false);
+    if unique_violation {
+        AppError::Conflict(format!("A etiqueta {} já existe", name))
+    } else {
+        e.into()
+    }
+}
+
+async fn find_label<'e, E>(
+    executor: E,
+    id: &str,
+    owner_id: &str,
+) -> Result<Option<Label>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = Sqlite>,
+{
+    sqlx::query_as::<_, Label>("SELECT * FROM labels WHERE id = ? AND owner_id = ?")
+        .bind(id)
+        .bind(owner_id)
+        .fetch_optional(executor)
+        .await
+}
+
+// Handler para listar as etiquetas do usuário
+#[utoipa::path(
+    tag = "etiquetas",
+    summary = "Lista as etiquetas do usuário",
+    responses((status = 200, body = ApiResponse<Vec<Label>>))
+)]
+#[get("/labels")]
+pub async fn get_labels(
+    db: web::Data<SqlitePool>,
+    user: AuthUser,
+) -> Result<impl Responder, AppError> {
+    let labels =
+        sqlx::query_as::<_, Label>("SELECT * FROM labels WHERE owner_id = ? ORDER BY name")
+            .bind(&user.id)
+            .fetch_all(db.get_ref())
+            .await?;
+    Ok(HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        message: "Etiquetas recuperadas com sucesso".to_string(),
+        data: Some(labels),
+    }))
+}
+
+// Handler para criar uma etiqueta
+#[utoipa::path(
+    tag = "etiquetas",
+    summary = "Cria uma etiqueta",
+    request_body = LabelInput,
+    responses(
+        (status = 201, body = ApiResponse<Label>),
+        (status = 409, description = "Já existe uma etiqueta com esse nome", body = Message),
+        (status = 422, description = "Nome ou cor inválidos", body = Message),
+    )
+)]
+#[post("/labels")]
+pub async fn create_label(
+    db: web::Data<SqlitePool>,
+    user: AuthUser,
+    input: web::Json<LabelInput>,
+) -> Result<impl Responder, AppError> {
+    let (name, color) = input
+        .into_inner()
+        .validate(DEFAULT_COLOR)
+        .map_err(AppError::validation)?;
+
+    let now = Utc::now();
+    let label = Label {
+        id: Uuid::new_v4().to_string(),
+        owner_id: user.id,
+        name,
+        color,
+        created_at: now,
+        updated_at: now,
+    };
+
+    sqlx::query(
+        "INSERT INTO labels (id, owner_id, name, color, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&label.id)
+    .bind(&label.owner_id)
+    .bind(&label.name)
+    .bind(&label.color)
+    .bind(label.created_at)
+    .bind(label.updated_at)
+    .execute(db.get_ref())
+    .await
+    .map_err(|e| save_error(e, &label.name))?;
+
+    Ok(HttpResponse::Created().json(ApiResponse {
+        success: true,
+        message: "Etiqueta criada com sucesso".to_string(),
+        data: Some(label),
+    }))
+}
+
+// Handler para renomear ou trocar a cor de uma etiqueta
+#[utoipa::path(
+    tag = "etiquetas",
+    summary = "Renomeia ou troca a cor de uma etiqueta",
+    request_body = LabelInput,
+    responses(
+        (status = 200, body = ApiResponse<Label>),
+        (status = 404, body = Message),
+        (status = 409, description = "Já existe uma etiqueta com esse nome", body = Message),
+        (status = 422, description = "Nome ou cor inválidos", body = Message),
+    )
+)]
+#[put("/labels/{id}")]
+pub async fn update_label(
+    db: web::Data<SqlitePool>,
+    user: AuthUser,
+    path: web::Path<String>,
+    input: web::Json<LabelInput>,
+) -> Result<impl Responder, AppError> {
+    let id = path.into_inner();
+
+    // Sem cor no corpo, mantém a cor atual
+    let current = find_label(db.get_ref(), &id, &user.id)
+        .await?
+        .ok_or_else(|| label_not_found(&id))?;
+    let (name, color) = input
+        .into_inner()
+        .validate(&current.color)
+        .map_err(AppError::validation)?;
+
+    let label = sqlx::query_as::<_, Label>(
+        "UPDATE labels SET name = ?, color = ?, updated_at = ? WHERE id = ? AND owner_id = ? RETURNING *",
+    )
+    .bind(&name)
+    .bind(&color)
+    .bind(Utc::now())
+    .bind(&id)
+    .bind(&user.id)
+    .fetch_optional(db.get_ref())
+    .await
+    .map_err(|e| save_error(e, &name))?
+    .ok_or_else(|| label_not_found(&id))?;
+    Ok(HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        message: "Etiqueta atualizada com sucesso".to_string(),
+        data: Some(label),
+    }))
+}
+
+// Handler para excluir uma etiqueta; ela é removida de todas as tarefas
+#[utoipa::path(
+    tag = "etiquetas",
+    summary = "Exclui uma etiqueta e a remove das tarefas",
+    responses(
+        (status = 200, body = Message),
+        (status = 404, body = Message),
+    )
+)]
+#[delete("/labels/{id}")]
+pub async fn delete_label(
+    db: web::Data<SqlitePool>,
+    user: AuthUser,
+    path: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    let id = path.into_inner();
+
+    let done = sqlx::query("DELETE FROM labels WHERE id = ? AND owner_id = ?")
+        .bind(&id)
+        .bind(&user.id)
+        .execute(db.get_ref())
+        .await?;
+    if done.rows_affected() == 0 {
+        return Err(label_not_found(&id));
+    }
+
+    Ok(HttpResponse::Ok().json(ApiResponse::<()> {
+        success: true,
+        message: format!("Etiqueta com ID {} excluída com sucesso", id),
+        data: None,
+    }))
+}
+
+// Tarefa com as etiquetas atuais, lida na transação antes de alterá-las
+async fn task_before_change(
+    tx: &mut Transaction<'_, Sqlite>,
+    task_id: &str,
+    user: &AuthUser,
+) -> Result<Task, AppError> {
+    find_task_details(&mut **tx, task_id, &user.id)
+        .await?
+        .ok_or_else(|| task_not_found(task_id))
+}
+
+// Recarrega a tarefa com as etiquetas, registra a diferença no histórico, grava a
+// transação, publica a alteração e devolve a tarefa na resposta
+async fn labels_changed(
+    mut tx: Transaction<'_, Sqlite>,
+    events: &TaskEvents,
+    before: Task,
+    actor_id: &str,
+    message: &str,
+) -> Result<HttpResponse, AppError> {
+    let task = find_task_details(&mut *tx, &before.id, &before.owner_id)
+        .await?
+        .ok_or_else(|| task_not_found(&before.id))?;
+    audit::record(
+        &mut tx,
+        actor_id,
+        TaskEventKind::Updated,
+        Some(&before),
+        Some(&task),
+    )
+    .await?;
+    tx.commit().await?;
+    events.publish(TaskEventKind::Updated, task.clone());
+    Ok(HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        message: message.to_string(),
+        data: Some(task),
+    }))
+}
+
+// Handler para aplicar uma etiqueta à tarefa; aplicar de novo não tem efeito
+#[utoipa::path(
+    tag = "etiquetas",
+    summary = "Aplica uma etiqueta à tarefa",
+    responses(
+        (status = 200, description = "Tarefa com as etiquetas atualizadas", body = ApiResponse<Task>),
+        (status = 404, body = Message),
+    )
+)]
+#[put("/tasks/{id}/labels/{label_id}")]
+pub async fn attach_label(
+    db: web::Data<SqlitePool>,
+    events: web::Data<TaskEvents>,
+    user: AuthUser,
+    path: web::Path<(String, String)>,
+) -> Result<impl Responder, AppError> {
+    let (task_id, label_id) = path.into_inner();
+
+    let mut tx = db.begin().await?;
+    let before = task_before_change(&mut tx, &task_id, &user).await?;
+    if find_label(&mut *tx, &label_id, &user.id).await?.is_none() {
+        return Err(label_not_found(&label_id));
+    }
+    sqlx::query("INSERT INTO task_labels (task_id, label_id) VALUES (?, ?) ON CONFLICT DO NOTHING")
+        .bind(&task_id)
+        .bind(&label_id)
+        .execute(&mut *tx)
+        .await?;
+
+    labels_changed(
+        tx,
+        &events,
+        before,
+        &user.id,
+        "Etiqueta aplicada com sucesso",
+    )
+    .await
+}
+
+// Handler para remover uma etiqueta da tarefa
+#[utoipa::path(
+    tag = "etiquetas",
+    summary = "Remove uma etiqueta da tarefa",
+    responses(
+        (status = 200, description = "Tarefa com as etiquetas atualizadas", body = ApiResponse<Task>),
+        (status = 404, body = Message),
+    )
+)]
+#[delete("/tasks/{id}/labels/{label_id}")]
+pub async fn detach_label(
+    db: web::Data<SqlitePool>,
+    events: web::Data<TaskEvents>,
+    user: AuthUser,
+    path: web::Path<(String, String)>,
+) -> Result<impl Responder, AppError> {
+    let (task_id, label_id) = path.into_inner();
+
+    let mut tx = db.begin().await?;
+    let before = task_before_change(&mut tx, &task_id, &user).await?;
+    let done = sqlx::query("DELETE FROM task_labels WHERE task_id = ? AND label_id = ?")
+        .bind(&task_id)
+        .bind(&label_id)
+        .execute(&mut *tx)
+        .await?;
+    if done.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!(
+            "A tarefa {} não possui a etiqueta {}",
+            task_id, label_id
+        )));
+    }
+
+    labels_changed(
+        tx,
+        &events,
+        before,
+        &user.id,
+        "Etiqueta removida com sucesso",
+    )
+    .await
+}
@@ -1,54 +1,304 @@
 use actix_cors::Cors;
 use actix_web::{
-    delete, get, post, put, web, App, HttpResponse, HttpServer, Responder, Result,
+    delete,
+    error::{JsonPayloadError, QueryPayloadError},
+    get,
+    http::{header, StatusCode},
+    middleware, post, put, web, App, HttpRequest, HttpResponse, HttpServer, Responder,
 };
 use chrono::{DateTime, Utc};
 use dotenv::dotenv;
-use serde::{Deserialize, Serialize};
-use sqlx::{sqlite::SqlitePool, FromRow};
+use serde::{Deserialize, Deserializer, Serialize};
+use sqlx::{
+    sqlite::{SqliteConnection, SqlitePool},
+    types::Json,
+    FromRow,
+};
 use std::env;
+use std::sync::Arc;
+use ts_rs::TS;
+use utoipa::ToSchema;
 use uuid::Uuid;
+use validator::Validate;
+
+mod attachments;
+mod audit;
+mod auth;
+mod batch;
+mod comments;
+mod dependencies;
+mod error;
+mod etag;
+mod events;
+mod frontend;
+mod idempotency;
+mod labels;
+mod migrations;
+mod openapi;
+mod priority;
+mod projects;
+mod query;
+mod recurrence;
+mod reminders;
+mod search;
+mod stats;
+mod status;
+mod storage;
+mod subtasks;
+mod sync;
+#[cfg(test)]
+mod typescript;
+mod validation;
+
+use attachments::Attachments;
+use auth::{AuthConfig, AuthUser};
+use error::AppError;
+use etag::{etag, IfMatch};
+use events::{TaskEventKind, TaskEvents};
+use frontend::Frontend;
+use idempotency::IdempotencyConfig;
+use priority::TaskPriority;
+use query::TaskQuery;
+use reminders::{LogNotifier, Reminders};
+use status::TaskStatus;
+use validation::ValidJson;
 
 // Modelo de tarefa
-#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, TS, ToSchema, Validate)]
 struct Task {
     #[serde(default)]
     id: String,
+    #[validate(
+        custom(function = "validation::not_blank", message = "O título é obrigatório"),
+        length(max = 100, message = "O título não pode exceder 100 caracteres")
+    )]
+    #[schema(min_length = 1, max_length = 100)]
     title: String,
+    #[validate(length(max = 10000, message = "A descrição não pode exceder 10000 caracteres"))]
+    #[schema(max_length = 10000)]
     description: String,
-    status: String,
+    #[serde(default)]
+    status: TaskStatus,
     #[serde(default = "default_priority")]
+    #[validate(custom(
+        function = "validation::priority",
+        message = "A prioridade deve estar entre 1 e 4"
+    ))]
+    #[schema(minimum = 1, maximum = 4)]
+    #[ts(as = "TaskPriority")]
     priority: i32,
     #[serde(default = "Utc::now")]
     created_at: DateTime<Utc>,
     #[serde(default = "Utc::now")]
     updated_at: DateTime<Utc>,
+    // Usuário dono da tarefa, definido pelo servidor a partir do token
+    #[serde(default)]
+    owner_id: String,
+    // Incrementada a cada alteração; exposta como ETag
+    #[serde(default = "default_version")]
+    #[ts(type = "number")]
+    version: i64,
+    // Projeto (quadro) ao qual a tarefa pertence
+    #[serde(default)]
+    #[ts(optional = nullable)]
+    project_id: Option<String>,
+    // Posição fracionária da tarefa dentro da coluna do quadro
+    #[serde(default)]
+    position: f64,
+    // Prazo da tarefa
+    #[serde(default)]
+    #[ts(optional = nullable)]
+    due_at: Option<DateTime<Utc>>,
+    // Momento em que o lembrete deve ser disparado
+    #[serde(default)]
+    #[ts(optional = nullable)]
+    remind_at: Option<DateTime<Utc>>,
+    // Regra de repetição no formato RRULE (ex.: "FREQ=WEEKLY;BYDAY=MO")
+    #[serde(default)]
+    #[ts(optional = nullable)]
+    recurrence: Option<String>,
+    // Série de ocorrências da tarefa recorrente e a posição da tarefa nela
+    #[serde(default)]
+    #[ts(optional = nullable)]
+    series_id: Option<String>,
+    #[serde(default = "default_occurrence")]
+    #[ts(type = "number")]
+    occurrence: i64,
+    // Tarefa pai, quando esta é uma subtarefa
+    #[serde(default)]
+    #[ts(optional = nullable)]
+    parent_id: Option<String>,
+    // Percentual de subtarefas concluídas; calculado nas consultas de leitura
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[sqlx(default)]
+    #[ts(optional, type = "number")]
+    progress: Option<i64>,
+    // Etiquetas da tarefa, com as cores; calculadas nas consultas de leitura
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[sqlx(default)]
+    #[ts(optional, as = "Option<Vec<labels::TaskLabel>>")]
+    #[schema(value_type = Option<Vec<labels::TaskLabel>>)]
+    labels: Option<Json<Vec<labels::TaskLabel>>>,
 }
 
-// Valor padrão para a prioridade
+// Valor padrão para a prioridade (Baixa)
 fn default_priority() -> i32 {
+    TaskPriority::Baixa as i32
+}
+
+// Versão inicial de uma tarefa
+fn default_version() -> i64 {
+    1
+}
+
+// Primeira ocorrência de uma série
+fn default_occurrence() -> i64 {
     1
 }
 
-// Modelo para atualização parcial de tarefas
-#[derive(Debug, Deserialize)]
+// Busca uma tarefa do usuário
+async fn find_task<'e, E>(
+    executor: E,
+    id: &str,
+    owner_id: &str,
+) -> Result<Option<Task>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query_as::<_, Task>("SELECT * FROM tasks WHERE id = ? AND owner_id = ?")
+        .bind(id)
+        .bind(owner_id)
+        .fetch_optional(executor)
+        .await
+}
+
+// SELECT das tarefas com os campos calculados (progresso das subtarefas e etiquetas),
+// usado nas rotas de leitura
+fn select_task_details() -> String {
+    format!(
+        "SELECT *, {}, {} FROM tasks",
+        subtasks::PROGRESS,
+        labels::LABELS
+    )
+}
+
+// Busca uma tarefa do usuário com os campos calculados
+async fn find_task_details<'e, E>(
+    executor: E,
+    id: &str,
+    owner_id: &str,
+) -> Result<Option<Task>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+{
+    sqlx::query_as::<_, Task>(&format!(
+        "{} WHERE id = ? AND owner_id = ?",
+        select_task_details()
+    ))
+    .bind(id)
+    .bind(owner_id)
+    .fetch_optional(executor)
+    .await
+}
+
+// Verifica se a tarefa existe e pertence ao usuário
+async fn check_task(db: &SqlitePool, task_id: &str, user: &AuthUser) -> Result<(), AppError> {
+    match find_task(db, task_id, &user.id).await? {
+        Some(_) => Ok(()),
+        None => Err(task_not_found(task_id)),
+    }
+}
+
+// Número de tentativas de gravação quando outra requisição altera a tarefa ao mesmo tempo
+const MAX_UPDATE_ATTEMPTS: usize = 3;
+
+// Modelo para atualização parcial de tarefas; os campos enviados seguem as regras de Task
+#[derive(Debug, Deserialize, TS, ToSchema, Validate)]
+#[ts(optional_fields)]
 struct TaskUpdate {
+    #[validate(
+        custom(function = "validation::not_blank", message = "O título é obrigatório"),
+        length(max = 100, message = "O título não pode exceder 100 caracteres")
+    )]
+    #[schema(min_length = 1, max_length = 100)]
     title: Option<String>,
+    #[validate(length(max = 10000, message = "A descrição não pode exceder 10000 caracteres"))]
+    #[schema(max_length = 10000)]
     description: Option<String>,
-    status: Option<String>,
+    status: Option<TaskStatus>,
+    #[validate(custom(
+        function = "validation::priority",
+        message = "A prioridade deve estar entre 1 e 4"
+    ))]
+    #[schema(minimum = 1, maximum = 4)]
+    #[ts(as = "Option<TaskPriority>")]
     priority: Option<i32>,
+    // Ausente mantém o valor atual; `null` remove o prazo ou o lembrete
+    #[serde(default, deserialize_with = "double_option")]
+    due_at: Option<Option<DateTime<Utc>>>,
+    #[serde(default, deserialize_with = "double_option")]
+    remind_at: Option<Option<DateTime<Utc>>>,
+    #[serde(default, deserialize_with = "double_option")]
+    recurrence: Option<Option<String>>,
+    // `null` transforma a subtarefa em uma tarefa independente
+    #[serde(default, deserialize_with = "double_option")]
+    parent_id: Option<Option<String>>,
+}
+
+// Distingue um campo ausente (None) de um campo enviado como null (Some(None))
+fn double_option<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
 }
 
 // Modelo de resposta da API
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, TS, ToSchema)]
+#[ts(bound = "T: TS")]
 struct ApiResponse<T> {
     success: bool,
     message: String,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
     data: Option<T>,
 }
 
+// Converte erros de leitura do JSON em respostas no formato ApiResponse.
+// Valores inválidos (como um status desconhecido) retornam 422, corpos acima de
+// JSON_BODY_LIMIT retornam 413 e JSON malformado retorna 400.
+fn json_error_handler(err: JsonPayloadError, _req: &HttpRequest) -> actix_web::Error {
+    match &err {
+        JsonPayloadError::Deserialize(e) if e.is_data() => {
+            AppError::validation(format!("Dados inválidos: {}", e)).into()
+        }
+        JsonPayloadError::Overflow { limit }
+        | JsonPayloadError::OverflowKnownLength { limit, .. } => {
+            AppError::PayloadTooLarge(format!("O corpo da requisição excede {} bytes", limit))
+                .into()
+        }
+        _ => AppError::BadRequest(format!("Requisição inválida: {}", err)).into(),
+    }
+}
+
+// Converte erros nos parâmetros da URL em respostas 400 no formato ApiResponse
+fn query_error_handler(err: QueryPayloadError, _req: &HttpRequest) -> actix_web::Error {
+    AppError::BadRequest(format!("Parâmetros inválidos: {}", err)).into()
+}
+
+// Erro 404 para uma tarefa inexistente ou de outro usuário
+fn task_not_found(id: &str) -> AppError {
+    AppError::NotFound(format!("Tarefa com ID {} não encontrada", id))
+}
+
 // Handler para a rota raiz
+#[utoipa::path(
+    tag = "geral",
+    summary = "Identificação da API",
+    security(()),
+    responses((status = 200, body = openapi::Message))
+)]
 #[get("/")]
 async fn index() -> impl Responder {
     HttpResponse::Ok().json(ApiResponse {
@@ -58,247 +308,657 @@ async fn index() -> impl Responder {
     })
 }
 
-// Handler para listar todas as tarefas
+// Handler para listar tarefas com filtros, ordenação e paginação por cursor
+#[utoipa::path(
+    tag = "tarefas",
+    summary = "Lista tarefas com filtros, ordenação e paginação por cursor",
+    params(TaskQuery),
+    responses(
+        (status = 200, body = ApiResponse<query::TaskPage>),
+        (status = 400, description = "Filtro ou cursor inválido", body = openapi::Message),
+    )
+)]
 #[get("/tasks")]
-async fn get_tasks(db: web::Data<SqlitePool>) -> Result<impl Responder> {
-    match sqlx::query_as::<_, Task>("SELECT * FROM tasks ORDER BY created_at DESC")
+async fn get_tasks(
+    db: web::Data<SqlitePool>,
+    user: AuthUser,
+    query: web::Query<TaskQuery>,
+) -> Result<impl Responder, AppError> {
+    let query = query.into_inner();
+
+    let mut builder = query.build(&user.id).map_err(AppError::BadRequest)?;
+    let tasks = builder
+        .build_query_as::<Task>()
         .fetch_all(db.get_ref())
-        .await
-    {
-        Ok(tasks) => Ok(HttpResponse::Ok().json(ApiResponse {
-            success: true,
-            message: "Tarefas recuperadas com sucesso".to_string(),
-            data: Some(tasks),
-        })),
-        Err(e) => {
-            log::error!("Erro ao listar tarefas: {}", e);
-            Ok(HttpResponse::InternalServerError().json(ApiResponse::<()> {
-                success: false,
-                message: format!("Erro ao recuperar tarefas: {}", e),
-                data: None,
-            }))
-        }
-    }
+        .await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        message: "Tarefas recuperadas com sucesso".to_string(),
+        data: Some(query.paginate(tasks)),
+    }))
 }
 
 // Handler para obter uma tarefa específica pelo ID
+#[utoipa::path(
+    tag = "tarefas",
+    summary = "Obtém uma tarefa",
+    responses(
+        (status = 200, description = "Tarefa, com a versão no cabeçalho ETag", body = ApiResponse<Task>),
+        (status = 404, body = openapi::Message),
+    )
+)]
 #[get("/tasks/{id}")]
-async fn get_task(db: web::Data<SqlitePool>, path: web::Path<String>) -> Result<impl Responder> {
+async fn get_task(
+    db: web::Data<SqlitePool>,
+    user: AuthUser,
+    path: web::Path<String>,
+) -> Result<impl Responder, AppError> {
     let id = path.into_inner();
 
-    match sqlx::query_as::<_, Task>("SELECT * FROM tasks WHERE id = ?")
-        .bind(&id)
-        .fetch_optional(db.get_ref())
-        .await
-    {
-        Ok(Some(task)) => Ok(HttpResponse::Ok().json(ApiResponse {
+    let task = find_task_details(db.get_ref(), &id, &user.id)
+        .await?
+        .ok_or_else(|| task_not_found(&id))?;
+    Ok(HttpResponse::Ok()
+        .insert_header((header::ETAG, etag(&task)))
+        .json(ApiResponse {
             success: true,
             message: "Tarefa recuperada com sucesso".to_string(),
             data: Some(task),
-        })),
-        Ok(None) => Ok(HttpResponse::NotFound().json(ApiResponse::<()> {
-            success: false,
-            message: format!("Tarefa com ID {} não encontrada", id),
-            data: None,
-        })),
-        Err(e) => {
-            log::error!("Erro ao buscar tarefa: {}", e);
-            Ok(HttpResponse::InternalServerError().json(ApiResponse::<()> {
-                success: false,
-                message: format!("Erro ao buscar tarefa: {}", e),
-                data: None,
-            }))
-        }
-    }
+        }))
 }
 
-// Handler para criar uma nova tarefa
-#[post("/tasks")]
-async fn create_task(
-    db: web::Data<SqlitePool>,
-    task: web::Json<Task>,
-) -> Result<impl Responder> {
-    let mut new_task = task.into_inner();
-    new_task.id = Uuid::new_v4().to_string();
+// Prepara uma tarefa recebida para inclusão com o ID informado: define o dono e os campos
+// controlados pelo servidor e normaliza a regra de recorrência. Os campos já vêm validados
+// pelas regras de #[validate(...)]; aqui ficam só as regras que envolvem mais de um campo.
+// Retorna a mensagem de erro (422) se os dados forem inválidos.
+fn prepare_new_task(mut new_task: Task, id: String, owner_id: String) -> Result<Task, String> {
+    new_task.title = new_task.title.trim().to_string();
+    new_task.id = id;
+    new_task.owner_id = owner_id;
     new_task.created_at = Utc::now();
     new_task.updated_at = Utc::now();
+    new_task.version = default_version();
+    new_task.occurrence = default_occurrence();
+    new_task.progress = None;
+    new_task.labels = None;
+
+    // Tarefas recorrentes começam uma série nova; a regra é gravada normalizada
+    new_task.recurrence = match new_task.recurrence.as_deref().map(recurrence::normalize) {
+        Some(Ok(_)) if new_task.due_at.is_none() => {
+            return Err("Tarefas recorrentes precisam de um prazo (due_at)".to_string())
+        }
+        Some(Ok(rule)) => Some(rule),
+        Some(Err(message)) => return Err(message),
+        None => None,
+    };
+    new_task.series_id = new_task.recurrence.as_ref().map(|_| new_task.id.clone());
+    Ok(new_task)
+}
+
+// Verifica o projeto e a tarefa pai e calcula a posição da nova tarefa na coluna.
+// Retorna a mensagem de erro (422) se alguma referência for inválida.
+async fn check_new_task(
+    conn: &mut SqliteConnection,
+    new_task: &mut Task,
+) -> Result<Result<(), String>, sqlx::Error> {
+    // A tarefa só pode ser criada em um projeto do próprio usuário
+    if let Some(project_id) = &new_task.project_id {
+        if projects::find_project(&mut *conn, project_id, &new_task.owner_id)
+            .await?
+            .is_none()
+        {
+            return Ok(Err(format!("Projeto com ID {} não encontrado", project_id)));
+        }
+    }
+
+    // A tarefa pai precisa ser do próprio usuário
+    if let Some(parent_id) = &new_task.parent_id {
+        if let Err(message) =
+            subtasks::check_parent(conn, &new_task.id, parent_id, &new_task.owner_id).await?
+        {
+            return Ok(Err(message));
+        }
+    }
 
-    match sqlx::query(
+    // Novas tarefas entram no fim da coluna
+    new_task.position = projects::next_position(
+        &mut *conn,
+        &new_task.owner_id,
+        new_task.project_id.as_deref(),
+        new_task.status,
+    )
+    .await?;
+    Ok(Ok(()))
+}
+
+// Grava a nova tarefa e o registro no histórico; deve rodar dentro de uma transação
+async fn insert_task(conn: &mut SqliteConnection, new_task: &Task) -> Result<(), sqlx::Error> {
+    sqlx::query(
         r#"
-        INSERT INTO tasks (id, title, description, status, priority, created_at, updated_at)
-        VALUES (?, ?, ?, ?, ?, ?, ?)
+        INSERT INTO tasks (id, title, description, status, priority, created_at, updated_at, owner_id, version, project_id, position, due_at, remind_at, recurrence, series_id, occurrence, parent_id)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         "#,
     )
     .bind(&new_task.id)
     .bind(&new_task.title)
     .bind(&new_task.description)
-    .bind(&new_task.status)
+    .bind(new_task.status)
     .bind(new_task.priority)
     .bind(new_task.created_at)
     .bind(new_task.updated_at)
-    .execute(db.get_ref())
+    .bind(&new_task.owner_id)
+    .bind(new_task.version)
+    .bind(&new_task.project_id)
+    .bind(new_task.position)
+    .bind(new_task.due_at)
+    .bind(new_task.remind_at)
+    .bind(&new_task.recurrence)
+    .bind(&new_task.series_id)
+    .bind(new_task.occurrence)
+    .bind(&new_task.parent_id)
+    .execute(&mut *conn)
+    .await?;
+    audit::record(
+        conn,
+        &new_task.owner_id,
+        TaskEventKind::Created,
+        None,
+        Some(new_task),
+    )
     .await
-    {
-        Ok(_) => Ok(HttpResponse::Created().json(ApiResponse {
-            success: true,
-            message: "Tarefa criada com sucesso".to_string(),
-            data: Some(new_task),
-        })),
-        Err(e) => {
-            log::error!("Erro ao criar tarefa: {}", e);
-            Ok(HttpResponse::InternalServerError().json(ApiResponse::<()> {
-                success: false,
-                message: format!("Erro ao criar tarefa: {}", e),
-                data: None,
-            }))
+}
+
+// Hash da tarefa recebida para a chave de idempotência. Só entram os campos que o cliente
+// controla, já que os demais são sempre gerados pelo servidor.
+fn input_fingerprint(task: &Task) -> String {
+    let mut input = task.clone();
+    input.id = String::new();
+    input.owner_id = String::new();
+    input.created_at = DateTime::<Utc>::UNIX_EPOCH;
+    input.updated_at = DateTime::<Utc>::UNIX_EPOCH;
+    input.version = default_version();
+    input.position = 0.0;
+    input.series_id = None;
+    input.occurrence = default_occurrence();
+    input.progress = None;
+    input.labels = None;
+    idempotency::fingerprint(&input)
+}
+
+// Handler para criar uma nova tarefa.
+// Com o cabeçalho Idempotency-Key, uma nova tentativa com o mesmo corpo recebe a
+// resposta original em vez de criar outra tarefa.
+#[utoipa::path(
+    tag = "tarefas",
+    summary = "Cria uma tarefa",
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Chave para repetir a criação com segurança"),
+    ),
+    request_body = Task,
+    responses(
+        (status = 201, description = "Tarefa criada, com a versão no cabeçalho ETag", body = ApiResponse<Task>),
+        (status = 400, description = "Idempotency-Key inválida", body = openapi::Message),
+        (status = 409, description = "Idempotency-Key em uso por outra requisição", body = openapi::Message),
+        (status = 422, description = "Dados inválidos ou chave já usada com outro corpo", body = openapi::Message),
+    )
+)]
+#[post("/tasks")]
+async fn create_task(
+    req: HttpRequest,
+    db: web::Data<SqlitePool>,
+    events: web::Data<TaskEvents>,
+    reminders: web::Data<Reminders>,
+    idempotency_config: web::Data<IdempotencyConfig>,
+    user: AuthUser,
+    task: ValidJson<Task>,
+) -> Result<HttpResponse, AppError> {
+    let task = task.into_inner();
+    let idempotency_key = idempotency::key_from_request(&req)?;
+    let request_hash = input_fingerprint(&task);
+
+    // Repete a resposta de uma tentativa anterior com a mesma chave
+    if let Some(key) = &idempotency_key {
+        if let Some(stored) = idempotency::lookup(db.get_ref(), &user.id, key).await? {
+            return stored.replay(&request_hash, key);
         }
     }
+
+    let mut new_task = prepare_new_task(task, Uuid::new_v4().to_string(), user.id.clone())
+        .map_err(AppError::validation)?;
+    check_new_task(&mut *db.acquire().await?, &mut new_task)
+        .await?
+        .map_err(AppError::validation)?;
+
+    let body = ApiResponse {
+        success: true,
+        message: "Tarefa criada com sucesso".to_string(),
+        data: Some(new_task.clone()),
+    };
+
+    // A resposta é guardada na mesma transação que cria a tarefa, então uma tarefa
+    // nunca é criada sem que as novas tentativas a encontrem
+    let result: Result<Result<(), &str>, sqlx::Error> = async {
+        let mut tx = db.begin().await?;
+        insert_task(&mut tx, &new_task).await?;
+        if let Some(key) = &idempotency_key {
+            let stored = idempotency::StoredResponse::new(
+                request_hash.clone(),
+                StatusCode::CREATED,
+                Some(etag(&new_task)),
+                serde_json::to_string(&body).unwrap_or_default(),
+            );
+            if !idempotency::store(&mut tx, &idempotency_config, &user.id, key, &stored).await? {
+                return Ok(Err(key.as_str()));
+            }
+        }
+        tx.commit().await?;
+        Ok(Ok(()))
+    }
+    .await;
+
+    // Uma requisição simultânea com a mesma chave criou a tarefa primeiro
+    if let Err(key) = result? {
+        return match idempotency::lookup(db.get_ref(), &user.id, key).await? {
+            Some(stored) => stored.replay(&request_hash, key),
+            None => Err(AppError::Conflict(format!(
+                "A chave de idempotência {} está em uso",
+                key
+            ))),
+        };
+    }
+
+    events.publish(TaskEventKind::Created, new_task.clone());
+    if new_task.remind_at.is_some() {
+        reminders.reschedule();
+    }
+    Ok(HttpResponse::Created()
+        .insert_header((header::ETAG, etag(&new_task)))
+        .json(body))
+}
+
+// Motivos para uma tentativa de gravação em update_task não ser aplicada
+enum UpdateRejection {
+    // A tarefa mudou entre a leitura e a gravação
+    Stale,
+    // A tarefa iria para Concluída com bloqueadoras ou subtarefas em aberto
+    Blocked(Vec<dependencies::TaskNode>),
+    // Alteração recusada pela validação (ex.: tarefa pai inválida)
+    Invalid(String),
+}
+
+// Erro 412 com a cópia atual da tarefa quando o If-Match não corresponde
+fn precondition_failed(task: Task) -> AppError {
+    AppError::PreconditionFailed {
+        message: format!(
+            "A tarefa com ID {} foi modificada por outra requisição",
+            task.id
+        ),
+        current: Some(Box::new(task)),
+    }
+}
+
+// Aplica a alteração parcial sobre a tarefa atual; apenas os campos fornecidos mudam.
+// Retorna a mensagem de erro (422) se a alteração for inválida.
+fn apply_update(existing: &Task, update: &TaskUpdate) -> Result<Task, String> {
+    let mut target = existing.clone();
+    target.status = update.status.unwrap_or(existing.status);
+
+    // Rejeita transições de status que não fazem parte do fluxo permitido
+    if !existing.status.can_transition_to(target.status) {
+        return Err(format!(
+            "Transição de status inválida: {} -> {}",
+            existing.status, target.status
+        ));
+    }
+
+    if let Some(title) = &update.title {
+        target.title = title.trim().to_string();
+    }
+    if let Some(description) = &update.description {
+        target.description = description.clone();
+    }
+    target.priority = update.priority.unwrap_or(existing.priority);
+    target.due_at = update.due_at.unwrap_or(existing.due_at);
+    target.remind_at = update.remind_at.unwrap_or(existing.remind_at);
+    target.recurrence = match &update.recurrence {
+        Some(Some(rule)) => Some(recurrence::normalize(rule)?),
+        Some(None) => None,
+        None => existing.recurrence.clone(),
+    };
+    if target.recurrence.is_some() && target.due_at.is_none() {
+        return Err("Tarefas recorrentes precisam de um prazo (due_at)".to_string());
+    }
+    target.series_id = existing
+        .series_id
+        .clone()
+        .or_else(|| target.recurrence.as_ref().map(|_| existing.id.clone()));
+    if let Some(parent_id) = &update.parent_id {
+        target.parent_id = parent_id.clone();
+    }
+    Ok(target)
 }
 
-// Handler para atualizar uma tarefa existente
+// Grava `target` sobre `existing` se a versão lida ainda for a atual, registra no histórico
+// e cria a próxima ocorrência de tarefas recorrentes. Deve rodar dentro de uma transação,
+// que o chamador só confirma quando a gravação não for recusada.
+async fn save_update(
+    conn: &mut SqliteConnection,
+    actor_id: &str,
+    existing: &Task,
+    target: &Task,
+) -> Result<Result<(Task, Option<Task>), UpdateRejection>, sqlx::Error> {
+    let updated = sqlx::query_as::<_, Task>(
+        r#"
+        UPDATE tasks
+        SET title = ?, description = ?, status = ?, priority = ?, updated_at = ?,
+            due_at = ?, remind_at = ?,
+            reminded_at = CASE WHEN remind_at IS ? THEN reminded_at END,
+            recurrence = ?, series_id = ?, parent_id = ?,
+            version = version + 1
+        WHERE id = ? AND owner_id = ? AND version = ?
+        RETURNING *
+        "#,
+    )
+    .bind(&target.title)
+    .bind(&target.description)
+    .bind(target.status)
+    .bind(target.priority)
+    .bind(Utc::now())
+    .bind(target.due_at)
+    .bind(target.remind_at)
+    .bind(target.remind_at)
+    .bind(&target.recurrence)
+    .bind(&target.series_id)
+    .bind(&target.parent_id)
+    .bind(&existing.id)
+    .bind(&existing.owner_id)
+    .bind(existing.version)
+    .fetch_optional(&mut *conn)
+    .await?;
+    let Some(updated) = updated else {
+        return Ok(Err(UpdateRejection::Stale));
+    };
+    // A nova tarefa pai é validada com o bloqueio de escrita já obtido,
+    // para que duas alterações simultâneas não formem um ciclo
+    if let Some(parent_id) = &updated.parent_id {
+        if updated.parent_id != existing.parent_id {
+            if let Err(message) =
+                subtasks::check_parent(conn, &existing.id, parent_id, &existing.owner_id).await?
+            {
+                return Ok(Err(UpdateRejection::Invalid(message)));
+            }
+        }
+    }
+    // Não conclui a tarefa enquanto houver bloqueadoras em aberto; a transação
+    // deve ser descartada sem commit
+    let blockers = dependencies::unfinished_blockers(conn, existing, &updated).await?;
+    if !blockers.is_empty() {
+        return Ok(Err(UpdateRejection::Blocked(blockers)));
+    }
+    audit::record(
+        conn,
+        actor_id,
+        TaskEventKind::Updated,
+        Some(existing),
+        Some(&updated),
+    )
+    .await?;
+    // Concluir uma ocorrência de tarefa recorrente cria a próxima
+    let next = recurrence::spawn_next(conn, actor_id, existing, &updated).await?;
+    Ok(Ok((updated, next)))
+}
+
+// Handler para atualizar uma tarefa existente.
+// A gravação só acontece se a versão lida ainda for a atual, de modo que
+// edições simultâneas nunca se sobrescrevem silenciosamente.
+#[utoipa::path(
+    tag = "tarefas",
+    summary = "Atualiza uma tarefa",
+    params(
+        ("If-Match" = Option<String>, Header, description = "ETag da versão editada"),
+    ),
+    request_body = TaskUpdate,
+    responses(
+        (status = 200, description = "Tarefa atualizada, com a nova versão no cabeçalho ETag", body = ApiResponse<Task>),
+        (status = 404, body = openapi::Message),
+        (status = 409, description = "Dependências não finalizadas bloqueiam a tarefa; traz as bloqueadoras", body = ApiResponse<Vec<dependencies::TaskNode>>),
+        (status = 412, description = "A versão atual não corresponde ao If-Match; traz a tarefa atual", body = ApiResponse<Task>),
+        (status = 422, description = "Transição de status ou dados inválidos", body = openapi::Message),
+    )
+)]
 #[put("/tasks/{id}")]
 async fn update_task(
+    req: HttpRequest,
     db: web::Data<SqlitePool>,
+    events: web::Data<TaskEvents>,
+    reminders: web::Data<Reminders>,
+    user: AuthUser,
     path: web::Path<String>,
-    update: web::Json<TaskUpdate>,
-) -> Result<impl Responder> {
+    update: ValidJson<TaskUpdate>,
+) -> Result<impl Responder, AppError> {
     let id = path.into_inner();
     let update = update.into_inner();
-    let now = Utc::now();
+    let if_match = IfMatch::from_request(&req);
 
-    // Primeiro, verifica se a tarefa existe
-    match sqlx::query_as::<_, Task>("SELECT * FROM tasks WHERE id = ?")
-        .bind(&id)
-        .fetch_optional(db.get_ref())
-        .await
-    {
-        Ok(Some(existing_task)) => {
-            // Atualiza apenas os campos que foram fornecidos
-            let title = update.title.unwrap_or(existing_task.title);
-            let description = update.description.unwrap_or(existing_task.description);
-            let status = update.status.unwrap_or(existing_task.status);
-            let priority = update.priority.unwrap_or(existing_task.priority);
-
-            match sqlx::query(
-                r#"
-                UPDATE tasks 
-                SET title = ?, description = ?, status = ?, priority = ?, updated_at = ?
-                WHERE id = ?
-                "#,
-            )
-            .bind(&title)
-            .bind(&description)
-            .bind(&status)
-            .bind(priority)
-            .bind(now)
-            .bind(&id)
-            .execute(db.get_ref())
-            .await
-            {
-                Ok(_) => {
-                    // Retorna a tarefa atualizada
-                    let updated_task = Task {
-                        id: existing_task.id,
-                        title,
-                        description,
-                        status,
-                        priority,
-                        created_at: existing_task.created_at,
-                        updated_at: now,
-                    };
-
-                    Ok(HttpResponse::Ok().json(ApiResponse {
+    for _ in 0..MAX_UPDATE_ATTEMPTS {
+        // Verifica se a tarefa existe e pertence ao usuário
+        let existing_task = find_task(db.get_ref(), &id, &user.id)
+            .await?
+            .ok_or_else(|| task_not_found(&id))?;
+
+        if let Some(condition) = &if_match {
+            if !condition.matches(existing_task.version) {
+                return Err(precondition_failed(existing_task));
+            }
+        }
+
+        let target = apply_update(&existing_task, &update).map_err(AppError::validation)?;
+
+        let result: Result<Result<(Task, Option<Task>), UpdateRejection>, sqlx::Error> = async {
+            let mut tx = db.begin().await?;
+            let saved = save_update(&mut tx, &user.id, &existing_task, &target).await?;
+            if saved.is_ok() {
+                tx.commit().await?;
+            }
+            Ok(saved)
+        }
+        .await;
+
+        match result? {
+            Ok((updated_task, next)) => {
+                events.publish(TaskEventKind::Updated, updated_task.clone());
+                if updated_task.remind_at != existing_task.remind_at {
+                    reminders.reschedule();
+                }
+                if let Some(next) = next {
+                    events.publish(TaskEventKind::Created, next);
+                    reminders.reschedule();
+                }
+
+                return Ok(HttpResponse::Ok()
+                    .insert_header((header::ETAG, etag(&updated_task)))
+                    .json(ApiResponse {
                         success: true,
                         message: "Tarefa atualizada com sucesso".to_string(),
                         data: Some(updated_task),
-                    }))
-                }
-                Err(e) => {
-                    log::error!("Erro ao atualizar tarefa: {}", e);
-                    Ok(HttpResponse::InternalServerError().json(ApiResponse::<()> {
-                        success: false,
-                        message: format!("Erro ao atualizar tarefa: {}", e),
-                        data: None,
-                    }))
-                }
+                    }));
             }
+            // A tarefa mudou entre a leitura e a gravação: lê novamente
+            Err(UpdateRejection::Stale) => continue,
+            Err(UpdateRejection::Blocked(blockers)) => return Err(AppError::Blocked(blockers)),
+            Err(UpdateRejection::Invalid(message)) => return Err(AppError::validation(message)),
         }
-        Ok(None) => Ok(HttpResponse::NotFound().json(ApiResponse::<()> {
-            success: false,
-            message: format!("Tarefa com ID {} não encontrada", id),
-            data: None,
-        })),
-        Err(e) => {
-            log::error!("Erro ao buscar tarefa: {}", e);
-            Ok(HttpResponse::InternalServerError().json(ApiResponse::<()> {
-                success: false,
-                message: format!("Erro ao buscar tarefa: {}", e),
-                data: None,
-            }))
+    }
+
+    Err(AppError::Conflict(format!(
+        "A tarefa com ID {} está sendo alterada por outra requisição, tente novamente",
+        id
+    )))
+}
+
+// Exclui a tarefa e suas subtarefas, em qualquer nível, na mesma instrução e registra
+// cada exclusão no histórico. Com `versions`, só exclui se a versão atual estiver na lista.
+// Retorna as tarefas excluídas; vazio quando a tarefa não existe ou a versão não confere.
+async fn delete_task_tree(
+    conn: &mut SqliteConnection,
+    actor_id: &str,
+    id: &str,
+    owner_id: &str,
+    versions: Option<&[i64]>,
+) -> Result<Vec<Task>, sqlx::Error> {
+    let mut query = sqlx::QueryBuilder::<sqlx::Sqlite>::new(
+        "WITH RECURSIVE subtree (id) AS (SELECT id FROM tasks WHERE id = ",
+    );
+    query
+        .push_bind(id)
+        .push(" AND owner_id = ")
+        .push_bind(owner_id);
+    if let Some(versions) = versions {
+        query.push(" AND version IN (");
+        let mut separated = query.separated(", ");
+        for version in versions {
+            separated.push_bind(*version);
         }
+        separated.push_unseparated(")");
+    }
+    query
+        .push(subtasks::SUBTREE_STEP)
+        .push(") DELETE FROM tasks WHERE id IN subtree RETURNING *");
+
+    let deleted = query.build_query_as::<Task>().fetch_all(&mut *conn).await?;
+    for task in &deleted {
+        audit::record(conn, actor_id, TaskEventKind::Deleted, Some(task), None).await?;
     }
+    Ok(deleted)
 }
 
-// Handler para excluir uma tarefa
+// Handler para excluir uma tarefa.
+// Com If-Match, a exclusão só acontece se a versão informada ainda for a atual.
+#[utoipa::path(
+    tag = "tarefas",
+    summary = "Exclui uma tarefa e suas subtarefas",
+    params(
+        ("If-Match" = Option<String>, Header, description = "ETag da versão que deve ser excluída"),
+    ),
+    responses(
+        (status = 200, body = openapi::Message),
+        (status = 404, body = openapi::Message),
+        (status = 412, description = "A versão atual não corresponde ao If-Match; traz a tarefa atual", body = ApiResponse<Task>),
+    )
+)]
 #[delete("/tasks/{id}")]
 async fn delete_task(
+    req: HttpRequest,
     db: web::Data<SqlitePool>,
+    events: web::Data<TaskEvents>,
+    attachments: web::Data<Attachments>,
+    user: AuthUser,
     path: web::Path<String>,
-) -> Result<impl Responder> {
+) -> Result<impl Responder, AppError> {
     let id = path.into_inner();
+    let if_match = IfMatch::from_request(&req);
 
-    match sqlx::query("DELETE FROM tasks WHERE id = ?")
-        .bind(&id)
-        .execute(db.get_ref())
-        .await
-    {
-        Ok(result) => {
-            if result.rows_affected() > 0 {
-                Ok(HttpResponse::Ok().json(ApiResponse::<()> {
-                    success: true,
-                    message: format!("Tarefa com ID {} excluída com sucesso", id),
-                    data: None,
-                }))
-            } else {
-                Ok(HttpResponse::NotFound().json(ApiResponse::<()> {
-                    success: false,
-                    message: format!("Tarefa com ID {} não encontrada", id),
-                    data: None,
-                }))
-            }
+    if let Some(condition) = &if_match {
+        let task = find_task(db.get_ref(), &id, &user.id)
+            .await?
+            .ok_or_else(|| task_not_found(&id))?;
+        if !condition.matches(task.version) {
+            return Err(precondition_failed(task));
         }
-        Err(e) => {
-            log::error!("Erro ao excluir tarefa: {}", e);
-            Ok(HttpResponse::InternalServerError().json(ApiResponse::<()> {
-                success: false,
-                message: format!("Erro ao excluir tarefa: {}", e),
-                data: None,
-            }))
+    }
+
+    // A condição de versão é repetida no DELETE para que a verificação e a exclusão sejam atômicas
+    let versions = match &if_match {
+        Some(IfMatch::Versions(versions)) => Some(versions.as_slice()),
+        _ => None,
+    };
+    let result: Result<Vec<Task>, sqlx::Error> = async {
+        let mut tx = db.begin().await?;
+        let deleted = delete_task_tree(&mut tx, &user.id, &id, &user.id, versions).await?;
+        if !deleted.is_empty() {
+            tx.commit().await?;
         }
+        Ok(deleted)
     }
-}
+    .await;
 
-// Inicializa o banco de dados
-async fn init_database(pool: &SqlitePool) -> Result<(), sqlx::Error> {
-    // Cria a tabela de tarefas se ela não existir
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS tasks (
-            id TEXT PRIMARY KEY,
-            title TEXT NOT NULL,
-            description TEXT NOT NULL,
-            status TEXT NOT NULL,
-            priority INTEGER NOT NULL DEFAULT 1,
-            created_at TEXT NOT NULL,
-            updated_at TEXT NOT NULL
-        )
-        "#,
-    )
-    .execute(pool)
-    .await?;
+    let tasks = result?;
+    if tasks.is_empty() {
+        return Err(match if_match {
+            Some(_) => AppError::PreconditionFailed {
+                message: format!(
+                    "A tarefa com ID {} foi modificada ou excluída por outra requisição",
+                    id
+                ),
+                current: None,
+            },
+            None => task_not_found(&id),
+        });
+    }
 
-    log::info!("Banco de dados inicializado com sucesso");
-    Ok(())
+    for task in tasks {
+        events.publish(TaskEventKind::Deleted, task);
+    }
+    // Os anexos saem junto com a tarefa (ON DELETE CASCADE); resta apagar os arquivos
+    attachments.cleanup(db.get_ref()).await;
+    Ok(HttpResponse::Ok().json(ApiResponse::<()> {
+        success: true,
+        message: format!("Tarefa com ID {} excluída com sucesso", id),
+        data: None,
+    }))
+}
+
+// Rotas da API. Todas precisam estar documentadas em openapi.rs, cujo teste monta o App
+// com esta mesma função e compara as rotas com o documento.
+fn api_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(index)
+        .service(auth::register)
+        .service(auth::login)
+        .service(auth::me)
+        .service(get_tasks)
+        // Deve vir antes de /tasks/{id} para não ser tratado como um ID
+        .service(events::task_events)
+        .service(reminders::get_overdue)
+        .service(reminders::get_upcoming)
+        .service(search::search_tasks)
+        .service(stats::get_stats)
+        .service(batch::batch_tasks)
+        .service(get_task)
+        .service(create_task)
+        .service(update_task)
+        .service(delete_task)
+        .service(projects::move_task)
+        .service(comments::get_comments)
+        .service(comments::create_comment)
+        .service(comments::update_comment)
+        .service(comments::delete_comment)
+        .service(comments::get_comment_history)
+        .service(attachments::get_attachments)
+        .service(attachments::upload_attachments)
+        .service(attachments::download_attachment)
+        .service(attachments::delete_attachment)
+        .service(audit::get_task_history)
+        .service(audit::get_activity)
+        .service(dependencies::add_dependency)
+        .service(dependencies::delete_dependency)
+        .service(dependencies::get_graph)
+        .service(labels::get_labels)
+        .service(labels::create_label)
+        .service(labels::update_label)
+        .service(labels::delete_label)
+        .service(labels::attach_label)
+        .service(labels::detach_label)
+        .service(recurrence::preview)
+        .service(projects::get_projects)
+        .service(projects::create_project)
+        .service(projects::get_project)
+        .service(projects::update_project)
+        .service(projects::delete_project)
+        .service(projects::update_columns)
+        .service(projects::get_board)
+        .service(sync::pull_changes)
+        .service(sync::push_changes);
 }
 
 #[actix_web::main]
@@ -312,16 +972,46 @@ async fn main() -> std::io::Result<()> {
         .unwrap_or_else(|_| "8080".to_string())
         .parse::<u16>()
         .expect("SERVER_PORT deve ser um número");
+    // Tamanho máximo, em bytes, do corpo JSON das requisições
+    let json_body_limit = env::var("JSON_BODY_LIMIT")
+        .unwrap_or_else(|_| "1048576".to_string())
+        .parse::<usize>()
+        .expect("JSON_BODY_LIMIT deve ser um número");
 
     // Conecta ao banco de dados SQLite
     let pool = SqlitePool::connect(&database_url)
         .await
         .expect("Falha ao conectar ao banco de dados");
 
-    // Inicializa o banco de dados
-    init_database(&pool)
+    // Com --migrate, apenas gerencia as migrações e encerra sem iniciar o servidor
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("--migrate") {
+        if let Err(e) = migrations::run_cli(&pool, &args[1..]).await {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    // Aplica as migrações pendentes antes de iniciar o servidor
+    migrations::run(&pool)
+        .await
+        .expect("Falha ao aplicar as migrações do banco de dados");
+    auth::init_legacy_user(&pool)
         .await
-        .expect("Falha ao inicializar o banco de dados");
+        .expect("Falha ao preparar o usuário legado");
+
+    // Canal de eventos compartilhado entre todos os workers
+    let events = web::Data::new(TaskEvents::new());
+    let auth_config = web::Data::new(AuthConfig::from_env());
+    let attachments = web::Data::new(Attachments::from_env());
+    let reminders = web::Data::new(Reminders::new());
+    let idempotency_config = web::Data::new(IdempotencyConfig::from_env());
+    let frontend = web::Data::new(Frontend::from_env().await);
+    reminders.clone().into_inner().spawn(
+        pool.clone(),
+        vec![Arc::new(LogNotifier), events.clone().into_inner()],
+    );
 
     log::info!("Servidor iniciado em http://127.0.0.1:{}", server_port);
 
@@ -329,21 +1019,34 @@ async fn main() -> std::io::Result<()> {
     HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(pool.clone()))
+            .app_data(events.clone())
+            .app_data(auth_config.clone())
+            .app_data(attachments.clone())
+            .app_data(reminders.clone())
+            .app_data(idempotency_config.clone())
+            .app_data(frontend.clone())
+            .app_data(
+                web::JsonConfig::default()
+                    .limit(json_body_limit)
+                    .error_handler(json_error_handler),
+            )
+            .app_data(web::QueryConfig::default().error_handler(query_error_handler))
+            .wrap(middleware::from_fn(auth::authenticate))
             .wrap(
                 Cors::default()
                     .allow_any_origin()
                     .allow_any_method()
                     .allow_any_header()
+                    .expose_headers(vec![header::ETAG])
                     .max_age(3600),
             )
-            .service(index)
-            .service(get_tasks)
-            .service(get_task)
-            .service(create_task)
-            .service(update_task)
-            .service(delete_task)
+            // /openapi.json e /docs vêm antes do frontend, que atende às demais navegações
+            .configure(openapi::configure)
+            // Deve vir antes das rotas da API para atender às navegações do navegador
+            .configure(|cfg| frontend.configure(cfg))
+            .configure(api_routes)
     })
     .bind(("127.0.0.1", server_port))?
     .run()
     .await
-} 
\ No newline at end of file
+}
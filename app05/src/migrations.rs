@@ -0,0 +1,188 @@
+use chrono::Utc;
+use sqlx::migrate::{MigrateError, Migration, Migrator};
+use sqlx::sqlite::{SqliteConnection, SqlitePool};
+use sqlx::{Connection, Executor};
+
+// Migrações embutidas no binário a partir da pasta migrations/.
+// As versões aplicadas ficam registradas na tabela _migrations.
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+// Migração registrada na tabela _migrations
+#[derive(sqlx::FromRow)]
+struct Applied {
+    version: i64,
+    checksum: Vec<u8>,
+}
+
+// Cria a tabela de controle. Bancos migrados por versões anteriores registravam as versões
+// em _sqlx_migrations, a tabela padrão do sqlx; os registros são copiados uma única vez.
+async fn ensure_table(conn: &mut SqliteConnection) -> Result<(), sqlx::Error> {
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS _migrations (
+            version INTEGER PRIMARY KEY,
+            description TEXT NOT NULL,
+            checksum BLOB NOT NULL,
+            applied_at TEXT NOT NULL
+        )
+        "#,
+    )
+    .await?;
+
+    let legacy: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = '_sqlx_migrations')",
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+    if legacy {
+        let mut tx = conn.begin().await?;
+        tx.execute(
+            r#"
+            INSERT OR IGNORE INTO _migrations (version, description, checksum, applied_at)
+            SELECT version, description, checksum, installed_on FROM _sqlx_migrations
+            WHERE success
+            "#,
+        )
+        .await?;
+        tx.execute("DROP TABLE _sqlx_migrations").await?;
+        tx.commit().await?;
+    }
+    Ok(())
+}
+
+async fn list_applied(conn: &mut SqliteConnection) -> Result<Vec<Applied>, sqlx::Error> {
+    sqlx::query_as("SELECT version, checksum FROM _migrations ORDER BY version")
+        .fetch_all(conn)
+        .await
+}
+
+// Migrações de subida, em ordem de versão
+fn up_migrations() -> impl Iterator<Item = &'static Migration> {
+    MIGRATOR
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration())
+}
+
+// Aplica a migração e a registra na mesma transação
+async fn apply(conn: &mut SqliteConnection, migration: &Migration) -> Result<(), sqlx::Error> {
+    let mut tx = conn.begin().await?;
+    tx.execute(&*migration.sql).await?;
+    sqlx::query(
+        "INSERT INTO _migrations (version, description, checksum, applied_at) VALUES (?, ?, ?, ?)",
+    )
+    .bind(migration.version)
+    .bind(&*migration.description)
+    .bind(&*migration.checksum)
+    .bind(Utc::now())
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await
+}
+
+// Desfaz a migração pelo script .down.sql e remove o registro na mesma transação
+async fn undo(conn: &mut SqliteConnection, version: i64) -> Result<(), MigrateError> {
+    let migration = MIGRATOR
+        .iter()
+        .find(|m| m.version == version && m.migration_type.is_down_migration())
+        .ok_or(MigrateError::VersionMissing(version))?;
+
+    let mut tx = conn.begin().await?;
+    tx.execute(&*migration.sql).await?;
+    sqlx::query("DELETE FROM _migrations WHERE version = ?")
+        .bind(version)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+// Aplica as migrações pendentes. Falha se uma migração aplicada foi alterada ou não existe
+// mais no binário.
+pub async fn run(pool: &SqlitePool) -> Result<(), MigrateError> {
+    let mut conn = pool.acquire().await?;
+    ensure_table(&mut conn).await?;
+    let applied = list_applied(&mut conn).await?;
+
+    if let Some(missing) = applied
+        .iter()
+        .find(|a| up_migrations().all(|m| m.version != a.version))
+    {
+        return Err(MigrateError::VersionMissing(missing.version));
+    }
+    for migration in up_migrations() {
+        match applied.iter().find(|a| a.version == migration.version) {
+            Some(a) if a.checksum != *migration.checksum => {
+                return Err(MigrateError::VersionMismatch(migration.version))
+            }
+            Some(_) => {}
+            None => apply(&mut conn, migration).await?,
+        }
+    }
+    log::info!("Migrações aplicadas com sucesso");
+    Ok(())
+}
+
+// Lista as migrações conhecidas e se já foram aplicadas
+pub async fn status(pool: &SqlitePool) -> Result<(), MigrateError> {
+    let mut conn = pool.acquire().await?;
+    ensure_table(&mut conn).await?;
+    let applied = list_applied(&mut conn).await?;
+
+    for migration in up_migrations() {
+        let state = match applied.iter().find(|a| a.version == migration.version) {
+            Some(a) if a.checksum != *migration.checksum => "aplicada (checksum diferente)",
+            Some(_) => "aplicada",
+            None => "pendente",
+        };
+        println!(
+            "{:>4}  {:<30} {}",
+            migration.version, migration.description, state
+        );
+    }
+    Ok(())
+}
+
+// Desfaz migrações até a versão `target` (exclusiva).
+// Sem alvo, desfaz apenas a última migração aplicada.
+pub async fn revert(pool: &SqlitePool, target: Option<i64>) -> Result<(), MigrateError> {
+    let mut conn = pool.acquire().await?;
+    ensure_table(&mut conn).await?;
+    let mut versions: Vec<i64> = list_applied(&mut conn)
+        .await?
+        .into_iter()
+        .map(|a| a.version)
+        .collect();
+    let target = match target {
+        Some(target) => target,
+        None => versions.iter().rev().nth(1).copied().unwrap_or(0),
+    };
+
+    versions.retain(|version| *version > target);
+    for version in versions.into_iter().rev() {
+        undo(&mut conn, version).await?;
+    }
+    log::info!("Migrações revertidas até a versão {}", target);
+    Ok(())
+}
+
+// Trata o argumento --migrate da linha de comando:
+//   app05 --migrate run               aplica as migrações pendentes
+//   app05 --migrate status            mostra o estado das migrações
+//   app05 --migrate revert [versão]   desfaz a última migração (ou até a versão informada)
+pub async fn run_cli(pool: &SqlitePool, args: &[String]) -> Result<(), String> {
+    match args.first().map(String::as_str) {
+        Some("run") => run(pool).await.map_err(|e| e.to_string()),
+        Some("status") => status(pool).await.map_err(|e| e.to_string()),
+        Some("revert") => {
+            let target = match args.get(1) {
+                Some(raw) => Some(
+                    raw.parse::<i64>()
+                        .map_err(|_| format!("Versão inválida: {}", raw))?,
+                ),
+                None => None,
+            };
+            revert(pool, target).await.map_err(|e| e.to_string())
+        }
+        _ => Err("Uso: app05 --migrate <run|status|revert [versão]>".to_string()),
+    }
+}
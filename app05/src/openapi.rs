@@ -0,0 +1,264 @@
+use actix_web::web;
+use utoipa::{
+    openapi::{
+        path::{Operation, PathItem},
+        security::{HttpAuthScheme, HttpBuilder, SecurityRequirement, SecurityScheme},
+        ContentBuilder, Ref, ResponseBuilder,
+    },
+    Modify, OpenApi, ToSchema,
+};
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::error::FieldError;
+use crate::{
+    attachments, audit, auth, batch, comments, dependencies, events, labels, projects, recurrence,
+    reminders, search, stats, sync,
+};
+
+// Documento OpenAPI gerado a partir dos handlers e modelos. Toda rota registrada em main
+// precisa aparecer em `paths`; o teste abaixo compara as duas listas.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "API de Gerenciamento de Tarefas",
+        description = "Respostas no formato { success, message, data }; os erros trazem também \
+                       um código estável em code. As rotas exigem o token JWT obtido em \
+                       /auth/login, exceto as marcadas como públicas."
+    ),
+    paths(
+        crate::index,
+        auth::register,
+        auth::login,
+        auth::me,
+        crate::get_tasks,
+        crate::get_task,
+        crate::create_task,
+        crate::update_task,
+        crate::delete_task,
+        search::search_tasks,
+        stats::get_stats,
+        batch::batch_tasks,
+        reminders::get_overdue,
+        reminders::get_upcoming,
+        events::task_events,
+        comments::get_comments,
+        comments::create_comment,
+        comments::update_comment,
+        comments::delete_comment,
+        comments::get_comment_history,
+        attachments::get_attachments,
+        attachments::upload_attachments,
+        attachments::download_attachment,
+        attachments::delete_attachment,
+        audit::get_task_history,
+        audit::get_activity,
+        dependencies::add_dependency,
+        dependencies::delete_dependency,
+        dependencies::get_graph,
+        labels::get_labels,
+        labels::create_label,
+        labels::update_label,
+        labels::delete_label,
+        labels::attach_label,
+        labels::detach_label,
+        projects::get_projects,
+        projects::create_project,
+        projects::get_project,
+        projects::update_project,
+        projects::delete_project,
+        projects::update_columns,
+        projects::get_board,
+        projects::move_task,
+        recurrence::preview,
+        sync::pull_changes,
+        sync::push_changes,
+    ),
+    tags(
+        (name = "geral", description = "Informações da API"),
+        (name = "autenticação", description = "Cadastro, login e usuário atual"),
+        (name = "tarefas", description = "Tarefas, busca, estatísticas e operações em lote"),
+        (name = "lembretes", description = "Tarefas vencidas e com prazo próximo"),
+        (name = "eventos", description = "Alterações das tarefas em tempo real (SSE)"),
+        (name = "comentários", description = "Comentários das tarefas e suas revisões"),
+        (name = "anexos", description = "Arquivos anexados às tarefas"),
+        (name = "histórico", description = "Registro de auditoria das alterações"),
+        (name = "dependências", description = "Tarefas que bloqueiam outras"),
+        (name = "etiquetas", description = "Etiquetas e sua associação às tarefas"),
+        (name = "projetos", description = "Projetos, colunas e quadros"),
+        (name = "recorrência", description = "Regras de repetição das tarefas"),
+        (name = "sincronização", description = "Sincronização de clientes offline"),
+    ),
+    modifiers(&BearerAuth),
+    security(("bearer" = []))
+)]
+pub struct ApiDoc;
+
+// Resposta sem dados (erros e confirmações): um ApiResponse sem o campo data.
+// Os erros trazem também o código estável em `code` e, na validação, os campos recusados.
+// O tipo só descreve o JSON: as respostas são montadas por ApiResponse<()> e pelo
+// ErrorBody de error.rs, então os campos nunca são lidos.
+#[expect(
+    dead_code,
+    reason = "esquema da documentação; nenhuma resposta é montada com este tipo"
+)]
+#[derive(ToSchema)]
+pub struct Message {
+    success: bool,
+    message: String,
+    #[schema(example = "not_found")]
+    code: Option<String>,
+    errors: Option<Vec<FieldError>>,
+}
+
+// Autenticação pelo token JWT no cabeçalho Authorization. As rotas públicas declaram
+// `security(())`; as demais ganham a resposta 401 do extrator AuthUser.
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+
+        let unauthorized = ResponseBuilder::new()
+            .description("Token ausente, inválido ou expirado")
+            .content(
+                "application/json",
+                ContentBuilder::new()
+                    .schema(Some(Ref::from_schema_name("Message")))
+                    .build(),
+            )
+            .build();
+        for item in openapi.paths.paths.values_mut() {
+            for operation in operations_mut(item) {
+                let public = operation
+                    .security
+                    .as_ref()
+                    .is_some_and(|security| security.contains(&SecurityRequirement::default()));
+                if !public {
+                    operation
+                        .responses
+                        .responses
+                        .entry("401".to_string())
+                        .or_insert_with(|| unauthorized.clone().into());
+                }
+            }
+        }
+    }
+}
+
+fn operations_mut(item: &mut PathItem) -> impl Iterator<Item = &mut Operation> {
+    [
+        &mut item.get,
+        &mut item.post,
+        &mut item.put,
+        &mut item.patch,
+        &mut item.delete,
+    ]
+    .into_iter()
+    .flatten()
+}
+
+// Documento em /openapi.json e Swagger UI em /docs
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::redirect("/docs", "/docs/"))
+        .service(SwaggerUi::new("/docs/{_:.*}").url("/openapi.json", ApiDoc::openapi()));
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::introspection::IntrospectionTree;
+    use actix_web::test::{call_and_read_body_json, init_service, TestRequest};
+    use actix_web::{App, HttpResponse};
+    use serde_json::Value;
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    const REPORT_PATH: &str = "/__routes";
+
+    async fn routes_report(tree: web::Data<IntrospectionTree>) -> HttpResponse {
+        HttpResponse::Ok()
+            .content_type("application/json")
+            .body(tree.report_as_json())
+    }
+
+    // Rotas (método, caminho) montadas no App pela mesma função usada em main
+    async fn registered_routes() -> BTreeSet<(String, String)> {
+        let app = init_service(
+            App::new()
+                .configure(crate::api_routes)
+                .route(REPORT_PATH, web::get().to(routes_report)),
+        )
+        .await;
+        let request = TestRequest::get().uri(REPORT_PATH).to_request();
+        let report: Vec<Value> = call_and_read_body_json(&app, request).await;
+
+        let mut routes = BTreeSet::new();
+        for item in report {
+            let path = item["full_path"].as_str().unwrap().to_string();
+            if path == REPORT_PATH {
+                continue;
+            }
+            for method in item["methods"].as_array().unwrap() {
+                let method = method.as_str().unwrap().to_lowercase();
+                routes.insert((method, path.clone()));
+            }
+        }
+        routes
+    }
+
+    fn documented_routes() -> BTreeSet<(String, String)> {
+        let openapi = ApiDoc::openapi();
+        let mut routes = BTreeSet::new();
+        for (path, item) in &openapi.paths.paths {
+            let methods = [
+                ("get", item.get.is_some()),
+                ("post", item.post.is_some()),
+                ("put", item.put.is_some()),
+                ("patch", item.patch.is_some()),
+                ("delete", item.delete.is_some()),
+            ];
+            for (method, _) in methods.into_iter().filter(|(_, present)| *present) {
+                routes.insert((method.to_string(), path.clone()));
+            }
+        }
+        routes
+    }
+
+    #[actix_web::test]
+    async fn every_registered_route_is_documented() {
+        let registered = registered_routes().await;
+        let documented = documented_routes();
+        assert!(
+            registered.len() > 40,
+            "Poucas rotas encontradas: {:?}",
+            registered
+        );
+
+        let missing: Vec<_> = registered.difference(&documented).collect();
+        assert!(missing.is_empty(), "Rotas fora do OpenAPI: {:?}", missing);
+        let unknown: Vec<_> = documented.difference(&registered).collect();
+        assert!(
+            unknown.is_empty(),
+            "Rotas documentadas sem handler: {:?}",
+            unknown
+        );
+    }
+
+    #[test]
+    fn private_routes_require_the_token() {
+        let openapi = ApiDoc::openapi();
+        let register = openapi.paths.paths["/auth/register"].post.as_ref().unwrap();
+        assert!(!register.responses.responses.contains_key("401"));
+        let tasks = openapi.paths.paths["/tasks"].get.as_ref().unwrap();
+        assert!(tasks.responses.responses.contains_key("401"));
+    }
+}
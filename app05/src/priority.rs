@@ -0,0 +1,49 @@
+pub use definition::TaskPriority;
+
+// O código gerado por #[derive(TS)] para enums com valores explícitos usa from_str_radix.
+// Um allow no enum não alcança o impl gerado, então o enum fica sozinho neste módulo.
+#[allow(clippy::from_str_radix_10)]
+mod definition {
+    use ts_rs::TS;
+
+    // Prioridades de uma tarefa. As tarefas guardam o valor numérico (1 a 4); os nomes
+    // definem o enum TaskPriority do frontend, usado como tipo de Task.priority.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, TS)]
+    #[ts(repr(enum))]
+    pub enum TaskPriority {
+        Baixa = 1,
+        Media = 2,
+        Alta = 3,
+        Urgente = 4,
+    }
+}
+
+impl TaskPriority {
+    // Prioridade correspondente ao valor gravado na tarefa
+    pub fn from_value(value: i32) -> Option<TaskPriority> {
+        match value {
+            1 => Some(TaskPriority::Baixa),
+            2 => Some(TaskPriority::Media),
+            3 => Some(TaskPriority::Alta),
+            4 => Some(TaskPriority::Urgente),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Os valores precisam acompanhar a faixa aceita em Task.priority e o padrão Baixa
+    #[test]
+    fn values_match_task_priorities() {
+        for value in 1..=4 {
+            let priority = TaskPriority::from_value(value).unwrap();
+            assert_eq!(priority as i32, value);
+        }
+        assert_eq!(TaskPriority::from_value(0), None);
+        assert_eq!(TaskPriority::from_value(5), None);
+        assert_eq!(crate::default_priority(), TaskPriority::Baixa as i32);
+    }
+}
@@ -0,0 +1,779 @@
+use actix_web::{delete, get, http::header, post, put, web, HttpRequest, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{sqlite::SqlitePool, FromRow, Sqlite, SqliteConnection};
+use std::collections::HashSet;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::attachments::Attachments;
+use crate::audit;
+use crate::auth::AuthUser;
+use crate::dependencies::{self, TaskNode};
+use crate::error::AppError;
+use crate::etag::{etag, IfMatch};
+use crate::events::{TaskEventKind, TaskEvents};
+use crate::openapi::Message;
+use crate::recurrence;
+use crate::reminders::Reminders;
+use crate::status::TaskStatus;
+use crate::subtasks;
+use crate::{
+    find_task, precondition_failed, select_task_details, task_not_found, ApiResponse, Task,
+};
+
+// Distância entre posições ao adicionar uma tarefa no fim de uma coluna
+const POSITION_STEP: f64 = 1024.0;
+// Abaixo desta distância entre vizinhos as posições da coluna são renumeradas
+const MIN_POSITION_GAP: f64 = 1e-6;
+
+// Modelo de projeto (quadro)
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct Project {
+    pub id: String,
+    pub name: String,
+    pub owner_id: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Coluna de um quadro, associada a um status
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct ProjectColumn {
+    pub status: TaskStatus,
+    pub name: String,
+    #[serde(default)]
+    pub position: i64,
+}
+
+// Dados para criar ou renomear um projeto
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ProjectInput {
+    name: String,
+}
+
+// Projeto com suas colunas
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProjectDetails {
+    #[serde(flatten)]
+    project: Project,
+    columns: Vec<ProjectColumn>,
+}
+
+// Coluna do quadro com as tarefas ordenadas por posição
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BoardColumn {
+    #[serde(flatten)]
+    column: ProjectColumn,
+    tasks: Vec<Task>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Board {
+    #[serde(flatten)]
+    project: Project,
+    columns: Vec<BoardColumn>,
+}
+
+// Destino de uma tarefa movida no quadro
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TaskMove {
+    // Coluna (status) de destino
+    status: TaskStatus,
+    // Tarefa que deve ficar imediatamente antes; sem ela a tarefa vai para o topo
+    after_id: Option<String>,
+    // Projeto de destino, para mover a tarefa entre quadros
+    project_id: Option<String>,
+}
+
+// Busca um projeto do usuário
+pub async fn find_project<'e, E>(
+    executor: E,
+    id: &str,
+    owner_id: &str,
+) -> Result<Option<Project>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = Sqlite>,
+{
+    sqlx::query_as::<_, Project>("SELECT * FROM projects WHERE id = ? AND owner_id = ?")
+        .bind(id)
+        .bind(owner_id)
+        .fetch_optional(executor)
+        .await
+}
+
+async fn find_columns(
+    db: &SqlitePool,
+    project_id: &str,
+) -> Result<Vec<ProjectColumn>, sqlx::Error> {
+    sqlx::query_as::<_, ProjectColumn>(
+        "SELECT status, name, position FROM project_columns WHERE project_id = ? ORDER BY position",
+    )
+    .bind(project_id)
+    .fetch_all(db)
+    .await
+}
+
+// Posição para uma nova tarefa no fim da coluna do usuário
+pub async fn next_position<'e, E>(
+    executor: E,
+    owner_id: &str,
+    project_id: Option<&str>,
+    status: TaskStatus,
+) -> Result<f64, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = Sqlite>,
+{
+    let max: Option<f64> = sqlx::query_scalar(
+        "SELECT MAX(position) FROM tasks WHERE owner_id = ? AND project_id IS ? AND status = ?",
+    )
+    .bind(owner_id)
+    .bind(project_id)
+    .bind(status)
+    .fetch_one(executor)
+    .await?;
+    Ok(max.map_or(POSITION_STEP, |max| max + POSITION_STEP))
+}
+
+fn project_not_found(id: &str) -> AppError {
+    AppError::NotFound(format!("Projeto com ID {} não encontrado", id))
+}
+
+fn invalid_name() -> AppError {
+    AppError::validation("O nome do projeto é obrigatório")
+}
+
+// Handler para listar os projetos do usuário
+#[utoipa::path(
+    tag = "projetos",
+    summary = "Lista os projetos do usuário",
+    responses((status = 200, body = ApiResponse<Vec<Project>>))
+)]
+#[get("/projects")]
+pub async fn get_projects(
+    db: web::Data<SqlitePool>,
+    user: AuthUser,
+) -> Result<impl Responder, AppError> {
+    let projects = sqlx::query_as::<_, Project>(
+        "SELECT * FROM projects WHERE owner_id = ? ORDER BY created_at DESC",
+    )
+    .bind(&user.id)
+    .fetch_all(db.get_ref())
+    .await?;
+    Ok(HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        message: "Projetos recuperados com sucesso".to_string(),
+        data: Some(projects),
+    }))
+}
+
+// Handler para criar um projeto com uma coluna para cada status
+#[utoipa::path(
+    tag = "projetos",
+    summary = "Cria um projeto com uma coluna para cada status",
+    request_body = ProjectInput,
+    responses(
+        (status = 201, body = ApiResponse<ProjectDetails>),
+        (status = 422, description = "Nome vazio", body = Message),
+    )
+)]
+#[post("/projects")]
+pub async fn create_project(
+    db: web::Data<SqlitePool>,
+    user: AuthUser,
+    input: web::Json<ProjectInput>,
+) -> Result<impl Responder, AppError> {
+    let name = input.into_inner().name.trim().to_string();
+    if name.is_empty() {
+        return Err(invalid_name());
+    }
+
+    let now = Utc::now();
+    let project = Project {
+        id: Uuid::new_v4().to_string(),
+        name,
+        owner_id: user.id,
+        created_at: now,
+        updated_at: now,
+    };
+    let columns: Vec<ProjectColumn> = [
+        TaskStatus::Pendente,
+        TaskStatus::EmAndamento,
+        TaskStatus::Concluida,
+        TaskStatus::Cancelada,
+    ]
+    .into_iter()
+    .enumerate()
+    .map(|(position, status)| ProjectColumn {
+        status,
+        name: status.to_string(),
+        position: position as i64,
+    })
+    .collect();
+
+    let mut tx = db.begin().await?;
+    sqlx::query(
+        "INSERT INTO projects (id, name, owner_id, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind(&project.id)
+    .bind(&project.name)
+    .bind(&project.owner_id)
+    .bind(project.created_at)
+    .bind(project.updated_at)
+    .execute(&mut *tx)
+    .await?;
+    insert_columns(&mut tx, &project.id, &columns).await?;
+    tx.commit().await?;
+
+    Ok(HttpResponse::Created().json(ApiResponse {
+        success: true,
+        message: "Projeto criado com sucesso".to_string(),
+        data: Some(ProjectDetails { project, columns }),
+    }))
+}
+
+async fn insert_columns(
+    conn: &mut SqliteConnection,
+    project_id: &str,
+    columns: &[ProjectColumn],
+) -> Result<(), sqlx::Error> {
+    for column in columns {
+        sqlx::query(
+            "INSERT INTO project_columns (project_id, status, name, position) VALUES (?, ?, ?, ?)",
+        )
+        .bind(project_id)
+        .bind(column.status)
+        .bind(&column.name)
+        .bind(column.position)
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
+
+// Handler para obter um projeto e suas colunas
+#[utoipa::path(
+    tag = "projetos",
+    summary = "Obtém um projeto e suas colunas",
+    responses(
+        (status = 200, body = ApiResponse<ProjectDetails>),
+        (status = 404, body = Message),
+    )
+)]
+#[get("/projects/{id}")]
+pub async fn get_project(
+    db: web::Data<SqlitePool>,
+    user: AuthUser,
+    path: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    let id = path.into_inner();
+
+    let project = find_project(db.get_ref(), &id, &user.id)
+        .await?
+        .ok_or_else(|| project_not_found(&id))?;
+
+    let columns = find_columns(db.get_ref(), &id).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        message: "Projeto recuperado com sucesso".to_string(),
+        data: Some(ProjectDetails { project, columns }),
+    }))
+}
+
+// Handler para renomear um projeto
+#[utoipa::path(
+    tag = "projetos",
+    summary = "Renomeia um projeto",
+    request_body = ProjectInput,
+    responses(
+        (status = 200, body = ApiResponse<Project>),
+        (status = 404, body = Message),
+        (status = 422, description = "Nome vazio", body = Message),
+    )
+)]
+#[put("/projects/{id}")]
+pub async fn update_project(
+    db: web::Data<SqlitePool>,
+    user: AuthUser,
+    path: web::Path<String>,
+    input: web::Json<ProjectInput>,
+) -> Result<impl Responder, AppError> {
+    let id = path.into_inner();
+    let name = input.into_inner().name.trim().to_string();
+    if name.is_empty() {
+        return Err(invalid_name());
+    }
+
+    let project = sqlx::query_as::<_, Project>(
+        "UPDATE projects SET name = ?, updated_at = ? WHERE id = ? AND owner_id = ? RETURNING *",
+    )
+    .bind(&name)
+    .bind(Utc::now())
+    .bind(&id)
+    .bind(&user.id)
+    .fetch_optional(db.get_ref())
+    .await?
+    .ok_or_else(|| project_not_found(&id))?;
+    Ok(HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        message: "Projeto atualizado com sucesso".to_string(),
+        data: Some(project),
+    }))
+}
+
+// Handler para excluir um projeto junto com suas tarefas e as subtarefas delas
+#[utoipa::path(
+    tag = "projetos",
+    summary = "Exclui um projeto com suas tarefas e as subtarefas delas",
+    responses(
+        (status = 200, body = Message),
+        (status = 404, body = Message),
+    )
+)]
+#[delete("/projects/{id}")]
+pub async fn delete_project(
+    db: web::Data<SqlitePool>,
+    events: web::Data<TaskEvents>,
+    attachments: web::Data<Attachments>,
+    user: AuthUser,
+    path: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    let id = path.into_inner();
+
+    let result: Result<Option<Vec<Task>>, sqlx::Error> = async {
+        let mut tx = db.begin().await?;
+        let deleted = sqlx::query("DELETE FROM projects WHERE id = ? AND owner_id = ?")
+            .bind(&id)
+            .bind(&user.id)
+            .execute(&mut *tx)
+            .await?;
+        if deleted.rows_affected() == 0 {
+            return Ok(None);
+        }
+        // As subtarefas saem junto com a tarefa pai, mesmo que estejam em outro projeto
+        let tasks = sqlx::query_as::<_, Task>(&format!(
+            r#"
+            WITH RECURSIVE subtree (id) AS (SELECT id FROM tasks WHERE project_id = ?{})
+            DELETE FROM tasks WHERE id IN subtree RETURNING *
+            "#,
+            subtasks::SUBTREE_STEP
+        ))
+        .bind(&id)
+        .fetch_all(&mut *tx)
+        .await?;
+        for task in &tasks {
+            audit::record(&mut tx, &user.id, TaskEventKind::Deleted, Some(task), None).await?;
+        }
+        tx.commit().await?;
+        Ok(Some(tasks))
+    }
+    .await;
+
+    let tasks = result?.ok_or_else(|| project_not_found(&id))?;
+    for task in tasks {
+        events.publish(TaskEventKind::Deleted, task);
+    }
+    attachments.cleanup(db.get_ref()).await;
+    Ok(HttpResponse::Ok().json(ApiResponse::<()> {
+        success: true,
+        message: format!("Projeto com ID {} excluído com sucesso", id),
+        data: None,
+    }))
+}
+
+// Handler para redefinir as colunas do quadro (ordem, nomes e status exibidos).
+// Uma coluna só pode ser removida se não houver tarefas do projeto naquele status.
+#[utoipa::path(
+    tag = "projetos",
+    summary = "Redefine as colunas do quadro",
+    request_body = Vec<ProjectColumn>,
+    responses(
+        (status = 200, body = ApiResponse<Vec<ProjectColumn>>),
+        (status = 404, body = Message),
+        (status = 409, description = "Há tarefas no status de uma coluna removida", body = Message),
+        (status = 422, description = "Coluna sem nome ou status repetido", body = Message),
+    )
+)]
+#[put("/projects/{id}/columns")]
+pub async fn update_columns(
+    db: web::Data<SqlitePool>,
+    user: AuthUser,
+    path: web::Path<String>,
+    input: web::Json<Vec<ProjectColumn>>,
+) -> Result<impl Responder, AppError> {
+    let id = path.into_inner();
+    let mut columns = input.into_inner();
+
+    let mut seen = HashSet::new();
+    for column in &mut columns {
+        column.name = column.name.trim().to_string();
+        if column.name.is_empty() || !seen.insert(column.status) {
+            return Err(AppError::validation(
+                "Cada coluna precisa de um nome e de um status diferente",
+            ));
+        }
+    }
+    for (position, column) in columns.iter_mut().enumerate() {
+        column.position = position as i64;
+    }
+
+    if find_project(db.get_ref(), &id, &user.id).await?.is_none() {
+        return Err(project_not_found(&id));
+    }
+
+    let used: Vec<TaskStatus> =
+        sqlx::query_scalar("SELECT DISTINCT status FROM tasks WHERE project_id = ?")
+            .bind(&id)
+            .fetch_all(db.get_ref())
+            .await?;
+    if let Some(missing) = used.iter().find(|status| !seen.contains(*status)) {
+        return Err(AppError::Conflict(format!(
+            "A coluna {} ainda possui tarefas e não pode ser removida",
+            missing
+        )));
+    }
+
+    let mut tx = db.begin().await?;
+    sqlx::query("DELETE FROM project_columns WHERE project_id = ?")
+        .bind(&id)
+        .execute(&mut *tx)
+        .await?;
+    insert_columns(&mut tx, &id, &columns).await?;
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        message: "Colunas atualizadas com sucesso".to_string(),
+        data: Some(columns),
+    }))
+}
+
+// Handler para obter o quadro: colunas na ordem definida, cada uma com suas tarefas
+#[utoipa::path(
+    tag = "projetos",
+    summary = "Obtém o quadro com as tarefas de cada coluna",
+    responses(
+        (status = 200, body = ApiResponse<Board>),
+        (status = 404, body = Message),
+    )
+)]
+#[get("/projects/{id}/board")]
+pub async fn get_board(
+    db: web::Data<SqlitePool>,
+    user: AuthUser,
+    path: web::Path<String>,
+) -> Result<impl Responder, AppError> {
+    let id = path.into_inner();
+
+    let project = find_project(db.get_ref(), &id, &user.id)
+        .await?
+        .ok_or_else(|| project_not_found(&id))?;
+
+    let columns = find_columns(db.get_ref(), &id).await?;
+    let mut tasks = sqlx::query_as::<_, Task>(&format!(
+        "{} WHERE project_id = ? ORDER BY position, id",
+        select_task_details()
+    ))
+    .bind(&id)
+    .fetch_all(db.get_ref())
+    .await?;
+
+    let columns = columns
+        .into_iter()
+        .map(|column| {
+            let (in_column, rest) = tasks
+                .drain(..)
+                .partition(|task: &Task| task.status == column.status);
+            tasks = rest;
+            BoardColumn {
+                column,
+                tasks: in_column,
+            }
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        message: "Quadro recuperado com sucesso".to_string(),
+        data: Some(Board { project, columns }),
+    }))
+}
+
+// Movimentação gravada: a tarefa movida, as tarefas renumeradas na coluna de destino
+// e a próxima ocorrência, quando a tarefa recorrente foi concluída
+struct Moved {
+    task: Task,
+    renumbered: Vec<Task>,
+    next: Option<Task>,
+}
+
+// Resultado de uma movimentação que não chegou a ser gravada
+enum MoveRejection {
+    TaskNotFound,
+    // A versão atual não corresponde ao If-Match
+    Stale,
+    ProjectNotFound(String),
+    Invalid(String),
+    Blocked(Vec<TaskNode>),
+}
+
+// Handler para mover uma tarefa dentro da coluna, entre colunas ou entre quadros.
+// Tudo acontece em uma transação iniciada por uma escrita na própria tarefa, o que
+// bloqueia outras movimentações até o commit e mantém a ordenação consistente.
+#[utoipa::path(
+    tag = "projetos",
+    summary = "Move uma tarefa no quadro ou entre quadros",
+    params(
+        ("If-Match" = Option<String>, Header, description = "ETag da versão movida"),
+    ),
+    request_body = TaskMove,
+    responses(
+        (status = 200, description = "Tarefa movida, com a nova versão no cabeçalho ETag", body = ApiResponse<Task>),
+        (status = 404, description = "Tarefa ou projeto não encontrado", body = Message),
+        (status = 409, description = "Tarefa bloqueada por dependências em aberto", body = ApiResponse<Vec<TaskNode>>),
+        (status = 412, description = "A versão atual não corresponde ao If-Match; traz a tarefa atual", body = ApiResponse<Task>),
+        (status = 422, description = "Coluna inexistente no quadro ou after_id fora dela", body = Message),
+    )
+)]
+#[post("/tasks/{id}/move")]
+pub async fn move_task(
+    req: HttpRequest,
+    db: web::Data<SqlitePool>,
+    events: web::Data<TaskEvents>,
+    reminders: web::Data<Reminders>,
+    user: AuthUser,
+    path: web::Path<String>,
+    input: web::Json<TaskMove>,
+) -> Result<impl Responder, AppError> {
+    let id = path.into_inner();
+    let input = input.into_inner();
+    let if_match = IfMatch::from_request(&req);
+
+    let result: Result<Result<Moved, MoveRejection>, sqlx::Error> = async {
+        let mut tx = db.begin().await?;
+
+        // A primeira instrução é uma escrita para obter o bloqueio de escrita do banco
+        let Some(task) = sqlx::query_as::<_, Task>(
+            r#"
+            UPDATE tasks SET version = version + 1, updated_at = ?
+            WHERE id = ? AND owner_id = ?
+            RETURNING *
+            "#,
+        )
+        .bind(Utc::now())
+        .bind(&id)
+        .bind(&user.id)
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            return Ok(Err(MoveRejection::TaskNotFound));
+        };
+        // A escrita acima já incrementou a versão; o If-Match se refere à anterior
+        if let Some(condition) = &if_match {
+            if !condition.matches(task.version - 1) {
+                return Ok(Err(MoveRejection::Stale));
+            }
+        }
+
+        if !task.status.can_transition_to(input.status) {
+            return Ok(Err(MoveRejection::Invalid(format!(
+                "Transição de status inválida: {} -> {}",
+                task.status, input.status
+            ))));
+        }
+
+        let project_id = input.project_id.clone().or(task.project_id.clone());
+        if let Some(project_id) = &project_id {
+            if find_project(&mut *tx, project_id, &user.id)
+                .await?
+                .is_none()
+            {
+                return Ok(Err(MoveRejection::ProjectNotFound(project_id.clone())));
+            }
+            let has_column: bool = sqlx::query_scalar(
+                "SELECT EXISTS (SELECT 1 FROM project_columns WHERE project_id = ? AND status = ?)",
+            )
+            .bind(project_id)
+            .bind(input.status)
+            .fetch_one(&mut *tx)
+            .await?;
+            if !has_column {
+                return Ok(Err(MoveRejection::Invalid(format!(
+                    "O quadro não possui a coluna {}",
+                    input.status
+                ))));
+            }
+        }
+
+        let (position, renumbered) = match position_after(
+            &mut tx,
+            &user.id,
+            &id,
+            project_id.as_deref(),
+            input.status,
+            input.after_id.as_deref(),
+        )
+        .await?
+        {
+            Some(found) => found,
+            None => {
+                return Ok(Err(MoveRejection::Invalid(format!(
+                    "A tarefa {} não está na coluna de destino",
+                    input.after_id.unwrap_or_default()
+                ))))
+            }
+        };
+
+        let moved = sqlx::query_as::<_, Task>(
+            "UPDATE tasks SET status = ?, project_id = ?, position = ? WHERE id = ? RETURNING *",
+        )
+        .bind(input.status)
+        .bind(&project_id)
+        .bind(position)
+        .bind(&id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let blockers = dependencies::unfinished_blockers(&mut tx, &task, &moved).await?;
+        if !blockers.is_empty() {
+            return Ok(Err(MoveRejection::Blocked(blockers)));
+        }
+
+        audit::record(
+            &mut tx,
+            &user.id,
+            TaskEventKind::Updated,
+            Some(&task),
+            Some(&moved),
+        )
+        .await?;
+        for (before, after) in &renumbered {
+            audit::record(
+                &mut tx,
+                &user.id,
+                TaskEventKind::Updated,
+                Some(before),
+                Some(after),
+            )
+            .await?;
+        }
+        // Mover uma tarefa recorrente para Concluída também cria a próxima ocorrência
+        let next = recurrence::spawn_next(&mut tx, &user.id, &task, &moved).await?;
+        tx.commit().await?;
+        let renumbered = renumbered.into_iter().map(|(_, after)| after).collect();
+        Ok(Ok(Moved {
+            task: moved,
+            renumbered,
+            next,
+        }))
+    }
+    .await;
+
+    match result? {
+        Ok(Moved {
+            task,
+            renumbered,
+            next,
+        }) => {
+            events.publish(TaskEventKind::Updated, task.clone());
+            for other in renumbered {
+                events.publish(TaskEventKind::Updated, other);
+            }
+            if let Some(next) = next {
+                events.publish(TaskEventKind::Created, next);
+                reminders.reschedule();
+            }
+            Ok(HttpResponse::Ok()
+                .insert_header((header::ETAG, etag(&task)))
+                .json(ApiResponse {
+                    success: true,
+                    message: "Tarefa movida com sucesso".to_string(),
+                    data: Some(task),
+                }))
+        }
+        Err(MoveRejection::TaskNotFound) => Err(task_not_found(&id)),
+        // A transação foi desfeita: a cópia atual é lida fora dela
+        Err(MoveRejection::Stale) => match find_task(db.get_ref(), &id, &user.id).await? {
+            Some(current) => Err(precondition_failed(current)),
+            None => Err(task_not_found(&id)),
+        },
+        Err(MoveRejection::ProjectNotFound(project_id)) => Err(project_not_found(&project_id)),
+        Err(MoveRejection::Invalid(message)) => Err(AppError::validation(message)),
+        Err(MoveRejection::Blocked(blockers)) => Err(AppError::Blocked(blockers)),
+    }
+}
+
+// Calcula a posição logo depois de `after_id` (ou no topo da coluna) entre as tarefas
+// do usuário. Retorna None se `after_id` não for uma tarefa do usuário na coluna de destino.
+// Junto com a posição vêm as tarefas renumeradas, como (antes, depois), para o histórico
+// e os eventos.
+async fn position_after(
+    conn: &mut SqliteConnection,
+    owner_id: &str,
+    task_id: &str,
+    project_id: Option<&str>,
+    status: TaskStatus,
+    after_id: Option<&str>,
+) -> Result<Option<(f64, Vec<(Task, Task)>)>, sqlx::Error> {
+    let column: Vec<Task> = sqlx::query_as(
+        r#"
+        SELECT * FROM tasks
+        WHERE owner_id = ? AND project_id IS ? AND status = ? AND id != ?
+        ORDER BY position, id
+        "#,
+    )
+    .bind(owner_id)
+    .bind(project_id)
+    .bind(status)
+    .bind(task_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let index = match after_id {
+        Some(after_id) => match column.iter().position(|task| task.id == after_id) {
+            Some(index) => index + 1,
+            None => return Ok(None),
+        },
+        None => 0,
+    };
+
+    let previous = index.checked_sub(1).map(|i| column[i].position);
+    let next = column.get(index).map(|task| task.position);
+    let mut renumbered = Vec::new();
+    let position = match (previous, next) {
+        (Some(previous), Some(next)) if next - previous > MIN_POSITION_GAP => {
+            (previous + next) / 2.0
+        }
+        (Some(_), Some(_)) => {
+            // Sem espaço entre os vizinhos (ou posições inválidas): renumera a coluna com
+            // intervalos de POSITION_STEP e coloca a tarefa no meio do intervalo do índice.
+            // As tarefas renumeradas mudam de versão como em qualquer outra alteração.
+            let now = Utc::now();
+            for (i, task) in column.into_iter().enumerate() {
+                let position = (i as f64 + 1.0) * POSITION_STEP;
+                if task.position == position {
+                    continue;
+                }
+                let updated = sqlx::query_as::<_, Task>(
+                    r#"
+                    UPDATE tasks SET position = ?, version = version + 1, updated_at = ?
+                    WHERE id = ? AND owner_id = ?
+                    RETURNING *
+                    "#,
+                )
+                .bind(position)
+                .bind(now)
+                .bind(&task.id)
+                .bind(owner_id)
+                .fetch_one(&mut *conn)
+                .await?;
+                renumbered.push((task, updated));
+            }
+            (index as f64 + 0.5) * POSITION_STEP
+        }
+        (Some(previous), None) => previous + POSITION_STEP,
+        (None, Some(next)) => next - POSITION_STEP,
+        (None, None) => POSITION_STEP,
+    };
+    Ok(Some((position, renumbered)))
+}
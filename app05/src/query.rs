@@ -0,0 +1,402 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{QueryBuilder, Sqlite};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::status::TaskStatus;
+use crate::{select_task_details, Task};
+
+// Tamanho padrão e máximo de uma página de tarefas
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 200;
+
+// Colunas pelas quais a listagem pode ser ordenada
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SortField {
+    #[default]
+    CreatedAt,
+    UpdatedAt,
+    Priority,
+    Title,
+    Position,
+}
+
+impl SortField {
+    fn column(self) -> &'static str {
+        match self {
+            SortField::CreatedAt => "created_at",
+            SortField::UpdatedAt => "updated_at",
+            SortField::Priority => "priority",
+            SortField::Title => "title",
+            SortField::Position => "position",
+        }
+    }
+
+    // Valor da coluna de ordenação para a tarefa, usado para montar o cursor
+    fn cursor_value(self, task: &Task) -> CursorValue {
+        match self {
+            SortField::CreatedAt => CursorValue::Date(task.created_at),
+            SortField::UpdatedAt => CursorValue::Date(task.updated_at),
+            SortField::Priority => CursorValue::Int(task.priority as i64),
+            SortField::Title => CursorValue::Text(task.title.clone()),
+            SortField::Position => CursorValue::Real(task.position),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+// Como combinar as etiquetas pedidas em `labels`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelMatch {
+    // Tarefas com pelo menos uma das etiquetas
+    #[default]
+    Any,
+    // Tarefas com todas as etiquetas
+    All,
+}
+
+// Parâmetros aceitos por GET /tasks
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct TaskQuery {
+    // Lista de status separados por vírgula (ex.: "Pendente,Em Andamento")
+    pub status: Option<String>,
+    pub priority_min: Option<i32>,
+    pub priority_max: Option<i32>,
+    pub created_from: Option<DateTime<Utc>>,
+    pub created_to: Option<DateTime<Utc>>,
+    pub updated_from: Option<DateTime<Utc>>,
+    pub updated_to: Option<DateTime<Utc>>,
+    // Busca parcial no título
+    pub q: Option<String>,
+    pub project_id: Option<String>,
+    // Subtarefas diretas de uma tarefa
+    pub parent_id: Option<String>,
+    // Nomes de etiquetas separados por vírgula (ex.: "bug,frontend")
+    pub labels: Option<String>,
+    #[serde(default)]
+    pub labels_match: LabelMatch,
+    #[serde(default)]
+    pub sort: SortField,
+    #[serde(default)]
+    pub order: SortOrder,
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+}
+
+// Página de tarefas retornada pela listagem
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TaskPage {
+    pub tasks: Vec<Task>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+// Posição da última tarefa de uma página: valor da coluna ordenada + id para desempate
+#[derive(Debug, Serialize, Deserialize)]
+struct Cursor {
+    value: CursorValue,
+    id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+enum CursorValue {
+    Int(i64),
+    Real(f64),
+    Date(DateTime<Utc>),
+    Text(String),
+}
+
+impl Cursor {
+    fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    fn decode(raw: &str, sort: SortField) -> Result<Self, String> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(raw)
+            .map_err(|_| "Cursor inválido".to_string())?;
+        let cursor: Cursor =
+            serde_json::from_slice(&bytes).map_err(|_| "Cursor inválido".to_string())?;
+
+        // O cursor precisa corresponder à coluna de ordenação atual
+        let matches = matches!(
+            (sort, &cursor.value),
+            (
+                SortField::CreatedAt | SortField::UpdatedAt,
+                CursorValue::Date(_)
+            ) | (SortField::Priority, CursorValue::Int(_))
+                | (SortField::Title, CursorValue::Text(_))
+                | (SortField::Position, CursorValue::Real(_))
+        );
+        if !matches {
+            return Err("Cursor não corresponde à ordenação solicitada".to_string());
+        }
+        Ok(cursor)
+    }
+}
+
+impl TaskQuery {
+    pub fn limit(&self) -> i64 {
+        self.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT)
+    }
+
+    fn statuses(&self) -> Result<Vec<TaskStatus>, String> {
+        match &self.status {
+            Some(raw) => raw
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::parse)
+                .collect(),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    // Nomes de etiquetas sem repetição; a comparação ignora maiúsculas como a coluna `labels.name`
+    fn label_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for name in self
+            .labels
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            if !names.iter().any(|seen| seen.eq_ignore_ascii_case(name)) {
+                names.push(name.to_string());
+            }
+        }
+        names
+    }
+
+    // Monta o SELECT com filtros, ordenação e paginação por cursor.
+    // Busca um registro a mais que o limite para saber se existe próxima página.
+    pub fn build<'a>(&'a self, owner_id: &'a str) -> Result<QueryBuilder<'a, Sqlite>, String> {
+        let statuses = self.statuses()?;
+        let cursor = match &self.cursor {
+            Some(raw) => Some(Cursor::decode(raw, self.sort)?),
+            None => None,
+        };
+
+        let mut qb = QueryBuilder::new(format!("{} WHERE owner_id = ", select_task_details()));
+        qb.push_bind(owner_id);
+
+        if !statuses.is_empty() {
+            qb.push(" AND status IN (");
+            let mut separated = qb.separated(", ");
+            for status in statuses {
+                separated.push_bind(status);
+            }
+            separated.push_unseparated(")");
+        }
+        if let Some(min) = self.priority_min {
+            qb.push(" AND priority >= ").push_bind(min);
+        }
+        if let Some(max) = self.priority_max {
+            qb.push(" AND priority <= ").push_bind(max);
+        }
+        if let Some(from) = self.created_from {
+            qb.push(" AND created_at >= ").push_bind(from);
+        }
+        if let Some(to) = self.created_to {
+            qb.push(" AND created_at <= ").push_bind(to);
+        }
+        if let Some(from) = self.updated_from {
+            qb.push(" AND updated_at >= ").push_bind(from);
+        }
+        if let Some(to) = self.updated_to {
+            qb.push(" AND updated_at <= ").push_bind(to);
+        }
+        if let Some(project_id) = &self.project_id {
+            qb.push(" AND project_id = ").push_bind(project_id);
+        }
+        if let Some(parent_id) = &self.parent_id {
+            qb.push(" AND parent_id = ").push_bind(parent_id);
+        }
+        let labels = self.label_names();
+        if !labels.is_empty() {
+            qb.push(
+                " AND id IN (SELECT tl.task_id FROM task_labels tl \
+                 JOIN labels l ON l.id = tl.label_id WHERE l.owner_id = ",
+            )
+            .push_bind(owner_id)
+            .push(" AND l.name IN (");
+            let count = labels.len() as i64;
+            let mut separated = qb.separated(", ");
+            for name in labels {
+                separated.push_bind(name);
+            }
+            separated.push_unseparated(")");
+            if self.labels_match == LabelMatch::All {
+                qb.push(" GROUP BY tl.task_id HAVING COUNT(*) = ")
+                    .push_bind(count);
+            }
+            qb.push(")");
+        }
+        if let Some(q) = self.q.as_deref().map(str::trim).filter(|q| !q.is_empty()) {
+            qb.push(" AND title LIKE ")
+                .push_bind(format!("%{}%", escape_like(q)))
+                .push(" ESCAPE '\\'");
+        }
+
+        let column = self.sort.column();
+        let (cmp, direction) = match self.order {
+            SortOrder::Asc => (">", "ASC"),
+            SortOrder::Desc => ("<", "DESC"),
+        };
+
+        if let Some(cursor) = cursor {
+            qb.push(format!(" AND ({} {} ", column, cmp));
+            push_cursor_value(&mut qb, &cursor.value);
+            qb.push(format!(" OR ({} = ", column));
+            push_cursor_value(&mut qb, &cursor.value);
+            qb.push(format!(" AND id {} ", cmp))
+                .push_bind(cursor.id)
+                .push("))");
+        }
+
+        qb.push(format!(
+            " ORDER BY {} {}, id {} LIMIT ",
+            column, direction, direction
+        ))
+        .push_bind(self.limit() + 1);
+
+        Ok(qb)
+    }
+
+    // Separa a página do registro excedente e gera o cursor da próxima página
+    pub fn paginate(&self, mut tasks: Vec<Task>) -> TaskPage {
+        let limit = self.limit() as usize;
+        let next_cursor = if tasks.len() > limit {
+            tasks.truncate(limit);
+            tasks.last().map(|last| {
+                Cursor {
+                    value: self.sort.cursor_value(last),
+                    id: last.id.clone(),
+                }
+                .encode()
+            })
+        } else {
+            None
+        };
+
+        TaskPage { tasks, next_cursor }
+    }
+}
+
+fn push_cursor_value(qb: &mut QueryBuilder<'_, Sqlite>, value: &CursorValue) {
+    match value {
+        CursorValue::Int(v) => qb.push_bind(*v),
+        CursorValue::Real(v) => qb.push_bind(*v),
+        CursorValue::Date(v) => qb.push_bind(*v),
+        CursorValue::Text(v) => qb.push_bind(v.clone()),
+    };
+}
+
+// Escapa os curingas do LIKE para que a busca seja literal
+fn escape_like(input: &str) -> String {
+    input
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SORTS: [SortField; 5] = [
+        SortField::CreatedAt,
+        SortField::UpdatedAt,
+        SortField::Priority,
+        SortField::Title,
+        SortField::Position,
+    ];
+
+    fn task() -> Task {
+        serde_json::from_value(serde_json::json!({
+            "id": "3f1c2b9e-0000-4000-8000-000000000001",
+            "title": "Relatório",
+            "description": "",
+            "priority": 3,
+            "position": 1536.5,
+            "created_at": "2024-03-01T10:00:00Z",
+            "updated_at": "2024-03-02T11:30:00.123Z",
+        }))
+        .unwrap()
+    }
+
+    fn cursor_for(sort: SortField) -> Cursor {
+        let task = task();
+        Cursor {
+            value: sort.cursor_value(&task),
+            id: task.id,
+        }
+    }
+
+    #[test]
+    fn cursor_round_trips_for_every_sort() {
+        for sort in SORTS {
+            let cursor = cursor_for(sort);
+            let decoded = Cursor::decode(&cursor.encode(), sort)
+                .unwrap_or_else(|e| panic!("{:?}: {}", sort, e));
+            assert_eq!(decoded.id, cursor.id);
+            assert_eq!(
+                serde_json::to_value(&decoded.value).unwrap(),
+                serde_json::to_value(&cursor.value).unwrap(),
+                "{:?}",
+                sort
+            );
+        }
+    }
+
+    #[test]
+    fn cursor_from_another_sort_is_rejected() {
+        for built_for in SORTS {
+            let raw = cursor_for(built_for).encode();
+            for sort in SORTS {
+                let same_column = sort == built_for
+                    || matches!(
+                        (sort, built_for),
+                        (SortField::CreatedAt, SortField::UpdatedAt)
+                            | (SortField::UpdatedAt, SortField::CreatedAt)
+                    );
+                assert_eq!(
+                    Cursor::decode(&raw, sort).is_ok(),
+                    same_column,
+                    "cursor de {:?} lido como {:?}",
+                    built_for,
+                    sort
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn malformed_cursor_is_rejected() {
+        assert!(Cursor::decode("não é base64", SortField::CreatedAt).is_err());
+        let not_json = URL_SAFE_NO_PAD.encode(b"{\"id\":");
+        assert!(Cursor::decode(&not_json, SortField::CreatedAt).is_err());
+    }
+
+    #[test]
+    fn escape_like_escapes_wildcards_and_backslash() {
+        assert_eq!(escape_like("100%"), "100\\%");
+        assert_eq!(escape_like("a_b"), "a\\_b");
+        assert_eq!(escape_like("c:\\tmp"), "c:\\\\tmp");
+        // A barra é escapada primeiro, para não duplicar as barras dos curingas
+        assert_eq!(escape_like("\\%_"), "\\\\\\%\\_");
+        assert_eq!(escape_like("texto comum"), "texto comum");
+    }
+}
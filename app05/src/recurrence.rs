@@ -0,0 +1,637 @@
+use actix_web::{get, web, HttpResponse, Responder};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday};
+use serde::Deserialize;
+use sqlx::sqlite::SqliteConnection;
+use std::fmt;
+use std::str::FromStr;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::events::TaskEventKind;
+use crate::openapi::Message;
+use crate::status::TaskStatus;
+use crate::{audit, projects, ApiResponse, Task};
+
+// Limites para regras que nunca produziriam uma próxima ocorrência
+const MAX_INTERVAL: u32 = 1000;
+const MAX_STEPS: usize = 1000;
+// Quantidade padrão e máxima de ocorrências em GET /recurrence/preview
+const DEFAULT_PREVIEW: usize = 5;
+const MAX_PREVIEW: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+// Regra de repetição no formato RRULE do iCalendar (RFC 5545), com o subconjunto:
+// FREQ=DAILY|WEEKLY|MONTHLY, INTERVAL, BYDAY, BYMONTHDAY, UNTIL e COUNT.
+// Exemplo: "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;COUNT=10". Horários em UTC.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    pub freq: Frequency,
+    pub interval: u32,
+    pub by_weekday: Vec<Weekday>,
+    // Dias do mês; valores negativos contam a partir do fim (-1 = último dia)
+    pub by_month_day: Vec<i32>,
+    pub until: Option<DateTime<Utc>>,
+    pub count: Option<u32>,
+}
+
+fn weekday_code(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+fn parse_weekday(code: &str) -> Result<Weekday, String> {
+    match code {
+        "MO" => Ok(Weekday::Mon),
+        "TU" => Ok(Weekday::Tue),
+        "WE" => Ok(Weekday::Wed),
+        "TH" => Ok(Weekday::Thu),
+        "FR" => Ok(Weekday::Fri),
+        "SA" => Ok(Weekday::Sat),
+        "SU" => Ok(Weekday::Sun),
+        _ => Err(format!("Dia da semana inválido em BYDAY: {}", code)),
+    }
+}
+
+// Aceita UNTIL no formato do iCalendar (20250131T000000Z), só a data ou RFC 3339
+fn parse_until(value: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(date) = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%SZ") {
+        return Ok(Utc.from_utc_datetime(&date));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y%m%d") {
+        return Ok(Utc.from_utc_datetime(&date.and_hms_opt(23, 59, 59).unwrap_or_default()));
+    }
+    DateTime::parse_from_rfc3339(value)
+        .map(|date| date.with_timezone(&Utc))
+        .map_err(|_| format!("UNTIL inválido: {}", value))
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let (next_year, next_month) = if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    };
+    NaiveDate::from_ymd_opt(next_year, next_month, 1)
+        .and_then(|first| first.pred_opt())
+        .map(|last| last.day())
+        .unwrap_or(28)
+}
+
+impl FromStr for RecurrenceRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let s = s.strip_prefix("RRULE:").unwrap_or(s);
+
+        let mut freq = None;
+        let mut interval = 1;
+        let mut by_weekday = Vec::new();
+        let mut by_month_day = Vec::new();
+        let mut until = None;
+        let mut count = None;
+
+        for part in s.split(';').filter(|part| !part.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("Parte inválida na regra: {}", part))?;
+            match key.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    freq = Some(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        _ => return Err(format!("FREQ não suportada: {}", value)),
+                    })
+                }
+                "INTERVAL" => {
+                    interval = value
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|interval| (1..=MAX_INTERVAL).contains(interval))
+                        .ok_or_else(|| format!("INTERVAL deve estar entre 1 e {}", MAX_INTERVAL))?
+                }
+                "BYDAY" => {
+                    for code in value.split(',') {
+                        let day = parse_weekday(&code.trim().to_ascii_uppercase())?;
+                        if !by_weekday.contains(&day) {
+                            by_weekday.push(day);
+                        }
+                    }
+                }
+                "BYMONTHDAY" => {
+                    for day in value.split(',') {
+                        let day = day
+                            .trim()
+                            .parse::<i32>()
+                            .ok()
+                            .filter(|day| *day != 0 && (-31..=31).contains(day))
+                            .ok_or_else(|| format!("BYMONTHDAY inválido: {}", day))?;
+                        if !by_month_day.contains(&day) {
+                            by_month_day.push(day);
+                        }
+                    }
+                }
+                "UNTIL" => until = Some(parse_until(value)?),
+                "COUNT" => {
+                    count = Some(
+                        value
+                            .parse::<u32>()
+                            .ok()
+                            .filter(|count| *count > 0)
+                            .ok_or_else(|| format!("COUNT inválido: {}", value))?,
+                    )
+                }
+                other => return Err(format!("Parte não suportada na regra: {}", other)),
+            }
+        }
+
+        let freq = freq.ok_or_else(|| "A regra precisa de FREQ".to_string())?;
+        if until.is_some() && count.is_some() {
+            return Err("UNTIL e COUNT não podem ser usados juntos".to_string());
+        }
+        if freq == Frequency::Monthly && !by_weekday.is_empty() {
+            return Err("BYDAY não é suportado com FREQ=MONTHLY".to_string());
+        }
+        if freq != Frequency::Monthly && !by_month_day.is_empty() {
+            return Err("BYMONTHDAY só pode ser usado com FREQ=MONTHLY".to_string());
+        }
+
+        by_weekday.sort_by_key(|day| day.num_days_from_monday());
+        Ok(RecurrenceRule {
+            freq,
+            interval,
+            by_weekday,
+            by_month_day,
+            until,
+            count,
+        })
+    }
+}
+
+// Forma normalizada da regra, gravada no banco e devolvida pela API
+impl fmt::Display for RecurrenceRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let freq = match self.freq {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+        };
+        write!(f, "FREQ={}", freq)?;
+        if self.interval != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if !self.by_weekday.is_empty() {
+            let days: Vec<&str> = self.by_weekday.iter().map(|d| weekday_code(*d)).collect();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+        if !self.by_month_day.is_empty() {
+            let days: Vec<String> = self.by_month_day.iter().map(|d| d.to_string()).collect();
+            write!(f, ";BYMONTHDAY={}", days.join(","))?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until.format("%Y%m%dT%H%M%SZ"))?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={}", count)?;
+        }
+        Ok(())
+    }
+}
+
+impl RecurrenceRule {
+    // Próxima data da série depois de `current`, mantendo o horário.
+    // Não considera UNTIL nem COUNT.
+    fn next_after(&self, current: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let interval = self.interval as i64;
+        match self.freq {
+            Frequency::Daily => {
+                let mut next = current;
+                for _ in 0..MAX_STEPS {
+                    next += Duration::days(interval);
+                    if self.by_weekday.is_empty() || self.by_weekday.contains(&next.weekday()) {
+                        return Some(next);
+                    }
+                }
+                None
+            }
+            Frequency::Weekly => {
+                let today = current.weekday().num_days_from_monday() as i64;
+                let days: Vec<i64> = if self.by_weekday.is_empty() {
+                    vec![today]
+                } else {
+                    self.by_weekday
+                        .iter()
+                        .map(|d| d.num_days_from_monday() as i64)
+                        .collect()
+                };
+                // Outro dia ainda nesta semana; senão, o primeiro dia da semana seguinte da série
+                if let Some(day) = days.iter().find(|day| **day > today) {
+                    return Some(current + Duration::days(day - today));
+                }
+                let monday = current - Duration::days(today);
+                Some(monday + Duration::weeks(interval) + Duration::days(days[0]))
+            }
+            Frequency::Monthly => {
+                let days = if self.by_month_day.is_empty() {
+                    vec![current.day() as i32]
+                } else {
+                    self.by_month_day.clone()
+                };
+                let time = current.time();
+                let (mut year, mut month) = (current.year(), current.month());
+                let mut first_month = true;
+
+                for _ in 0..MAX_STEPS {
+                    let last = days_in_month(year, month) as i32;
+                    // Dias inexistentes no mês (como 31 de abril) são pulados, como no iCalendar
+                    let mut candidates: Vec<u32> = days
+                        .iter()
+                        .map(|day| if *day < 0 { last + 1 + day } else { *day })
+                        .filter(|day| (1..=last).contains(day))
+                        .map(|day| day as u32)
+                        .filter(|day| !first_month || *day > current.day())
+                        .collect();
+                    candidates.sort_unstable();
+
+                    if let Some(day) = candidates.first() {
+                        let date = NaiveDate::from_ymd_opt(year, month, *day)?;
+                        return Some(Utc.from_utc_datetime(&date.and_time(time)));
+                    }
+
+                    let months = month as i64 - 1 + interval;
+                    year += (months / 12) as i32;
+                    month = (months % 12) as u32 + 1;
+                    first_month = false;
+                }
+                None
+            }
+        }
+    }
+
+    // Data da ocorrência `occurrence + 1`, sabendo que `current` é a ocorrência `occurrence`
+    pub fn next_occurrence(
+        &self,
+        current: DateTime<Utc>,
+        occurrence: i64,
+    ) -> Option<DateTime<Utc>> {
+        if let Some(count) = self.count {
+            if occurrence >= count as i64 {
+                return None;
+            }
+        }
+        self.next_after(current)
+            .filter(|next| self.until.is_none_or(|until| *next <= until))
+    }
+
+    // As próximas `n` datas da série, começando em `start` (inclusive)
+    pub fn preview(&self, start: DateTime<Utc>, n: usize) -> Vec<DateTime<Utc>> {
+        let mut dates = Vec::with_capacity(n);
+        let mut current =
+            Some(start).filter(|start| self.until.is_none_or(|until| *start <= until));
+        let mut occurrence = 1;
+        while let Some(date) = current {
+            if dates.len() == n {
+                break;
+            }
+            dates.push(date);
+            current = self.next_occurrence(date, occurrence);
+            occurrence += 1;
+        }
+        dates
+    }
+}
+
+// Valida e normaliza a regra recebida na API
+pub fn normalize(rule: &str) -> Result<String, String> {
+    rule.parse::<RecurrenceRule>().map(|rule| rule.to_string())
+}
+
+// Cria a próxima ocorrência quando uma tarefa recorrente é concluída.
+// Deve rodar na mesma transação da alteração de status.
+pub async fn spawn_next(
+    conn: &mut SqliteConnection,
+    actor_id: &str,
+    before: &Task,
+    after: &Task,
+) -> Result<Option<Task>, sqlx::Error> {
+    if before.status == TaskStatus::Concluida || after.status != TaskStatus::Concluida {
+        return Ok(None);
+    }
+    let (Some(rule), Some(due_at)) = (&after.recurrence, after.due_at) else {
+        return Ok(None);
+    };
+    let Some(next_due) = rule
+        .parse::<RecurrenceRule>()
+        .ok()
+        .and_then(|rule| rule.next_occurrence(due_at, after.occurrence))
+    else {
+        return Ok(None);
+    };
+
+    let now = Utc::now();
+    let mut next = after.clone();
+    next.id = Uuid::new_v4().to_string();
+    next.status = TaskStatus::default();
+    next.created_at = now;
+    next.updated_at = now;
+    next.version = 1;
+    next.occurrence = after.occurrence + 1;
+    next.due_at = Some(next_due);
+    // O lembrete mantém a mesma antecedência em relação ao prazo
+    next.remind_at = after
+        .remind_at
+        .map(|remind_at| next_due - (due_at - remind_at));
+    next.position = projects::next_position(
+        &mut *conn,
+        &next.owner_id,
+        next.project_id.as_deref(),
+        next.status,
+    )
+    .await?;
+
+    // A ocorrência seguinte só é criada uma vez, mesmo que a tarefa seja reaberta e concluída de novo
+    let inserted = sqlx::query(
+        r#"
+        INSERT INTO tasks (id, title, description, status, priority, created_at, updated_at, owner_id, version, project_id, position, due_at, remind_at, recurrence, series_id, occurrence, parent_id)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT (series_id, occurrence) DO NOTHING
+        "#,
+    )
+    .bind(&next.id)
+    .bind(&next.title)
+    .bind(&next.description)
+    .bind(next.status)
+    .bind(next.priority)
+    .bind(next.created_at)
+    .bind(next.updated_at)
+    .bind(&next.owner_id)
+    .bind(next.version)
+    .bind(&next.project_id)
+    .bind(next.position)
+    .bind(next.due_at)
+    .bind(next.remind_at)
+    .bind(&next.recurrence)
+    .bind(&next.series_id)
+    .bind(next.occurrence)
+    .bind(&next.parent_id)
+    .execute(&mut *conn)
+    .await?;
+    if inserted.rows_affected() == 0 {
+        return Ok(None);
+    }
+
+    audit::record(conn, actor_id, TaskEventKind::Created, None, Some(&next)).await?;
+    Ok(Some(next))
+}
+
+// Parâmetros de GET /recurrence/preview
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct PreviewQuery {
+    rule: String,
+    // Primeira ocorrência da série (padrão: agora)
+    start: Option<DateTime<Utc>>,
+    n: Option<usize>,
+}
+
+// Handler para visualizar as próximas ocorrências de uma regra antes de usá-la
+#[utoipa::path(
+    tag = "recorrência",
+    summary = "Lista as próximas ocorrências de uma regra de recorrência",
+    params(PreviewQuery),
+    security(()),
+    responses(
+        (status = 200, description = "Datas das ocorrências (RFC 3339, UTC)", body = ApiResponse<Vec<String>>),
+        (status = 422, description = "Regra inválida", body = Message),
+    )
+)]
+#[get("/recurrence/preview")]
+pub async fn preview(query: web::Query<PreviewQuery>) -> Result<impl Responder, AppError> {
+    let rule = query
+        .rule
+        .parse::<RecurrenceRule>()
+        .map_err(AppError::validation)?;
+    let n = query.n.unwrap_or(DEFAULT_PREVIEW).clamp(1, MAX_PREVIEW);
+    let start = query.start.unwrap_or_else(Utc::now);
+
+    Ok(HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        message: format!("Próximas ocorrências de {}", rule),
+        data: Some(rule.preview(start, n)),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(date: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(date)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn dates(rule: &str, start: &str, n: usize) -> Vec<String> {
+        rule.parse::<RecurrenceRule>()
+            .unwrap()
+            .preview(at(start), n)
+            .iter()
+            .map(|date| date.to_rfc3339())
+            .collect()
+    }
+
+    #[test]
+    fn parses_rule_parts() {
+        let rule: RecurrenceRule = "RRULE:freq=weekly;interval=2;byday=we,MO,mo;count=10"
+            .parse()
+            .unwrap();
+        assert_eq!(rule.freq, Frequency::Weekly);
+        assert_eq!(rule.interval, 2);
+        assert_eq!(rule.by_weekday, vec![Weekday::Mon, Weekday::Wed]);
+        assert_eq!(rule.count, Some(10));
+        assert_eq!(rule.until, None);
+
+        let rule: RecurrenceRule = "FREQ=MONTHLY;BYMONTHDAY=15,-1,15".parse().unwrap();
+        assert_eq!(rule.by_month_day, vec![15, -1]);
+    }
+
+    #[test]
+    fn rejects_invalid_rules() {
+        for rule in [
+            "",
+            "INTERVAL=2",
+            "FREQ",
+            "FREQ=YEARLY",
+            "FREQ=DAILY;INTERVAL=0",
+            "FREQ=DAILY;INTERVAL=1001",
+            "FREQ=DAILY;BYDAY=XX",
+            "FREQ=MONTHLY;BYMONTHDAY=0",
+            "FREQ=MONTHLY;BYMONTHDAY=32",
+            "FREQ=DAILY;COUNT=0",
+            "FREQ=DAILY;COUNT=2;UNTIL=20250101",
+            "FREQ=DAILY;UNTIL=amanhã",
+            "FREQ=MONTHLY;BYDAY=MO",
+            "FREQ=WEEKLY;BYMONTHDAY=1",
+            "FREQ=DAILY;BYHOUR=9",
+        ] {
+            assert!(rule.parse::<RecurrenceRule>().is_err(), "{}", rule);
+        }
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for rule in [
+            "FREQ=DAILY",
+            "FREQ=DAILY;BYDAY=MO,FR;COUNT=5",
+            "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE",
+            "FREQ=MONTHLY;BYMONTHDAY=1,-1;UNTIL=20251231T120000Z",
+        ] {
+            let parsed: RecurrenceRule = rule.parse().unwrap();
+            assert_eq!(parsed.to_string(), rule);
+            assert_eq!(parsed.to_string().parse::<RecurrenceRule>(), Ok(parsed));
+        }
+    }
+
+    #[test]
+    fn normalizes_rules() {
+        assert_eq!(
+            normalize("RRULE:freq=daily;interval=1").unwrap(),
+            "FREQ=DAILY"
+        );
+        assert_eq!(
+            normalize("FREQ=DAILY;UNTIL=20250131").unwrap(),
+            "FREQ=DAILY;UNTIL=20250131T235959Z"
+        );
+        assert_eq!(
+            normalize("FREQ=DAILY;UNTIL=2025-01-31T10:00:00-03:00").unwrap(),
+            "FREQ=DAILY;UNTIL=20250131T130000Z"
+        );
+    }
+
+    #[test]
+    fn day_31_skips_shorter_months() {
+        assert_eq!(
+            dates("FREQ=MONTHLY;BYMONTHDAY=31", "2025-01-31T09:00:00Z", 4),
+            [
+                "2025-01-31T09:00:00+00:00",
+                "2025-03-31T09:00:00+00:00",
+                "2025-05-31T09:00:00+00:00",
+                "2025-07-31T09:00:00+00:00",
+            ]
+        );
+        // Sem BYMONTHDAY vale o dia da data inicial
+        assert_eq!(
+            dates("FREQ=MONTHLY", "2025-01-31T09:00:00Z", 2),
+            ["2025-01-31T09:00:00+00:00", "2025-03-31T09:00:00+00:00"]
+        );
+    }
+
+    #[test]
+    fn negative_month_day_counts_from_month_end() {
+        assert_eq!(
+            dates("FREQ=MONTHLY;BYMONTHDAY=-1", "2025-01-31T09:00:00Z", 4),
+            [
+                "2025-01-31T09:00:00+00:00",
+                "2025-02-28T09:00:00+00:00",
+                "2025-03-31T09:00:00+00:00",
+                "2025-04-30T09:00:00+00:00",
+            ]
+        );
+        assert_eq!(
+            dates("FREQ=MONTHLY;BYMONTHDAY=-1", "2024-01-31T09:00:00Z", 2),
+            ["2024-01-31T09:00:00+00:00", "2024-02-29T09:00:00+00:00"]
+        );
+    }
+
+    #[test]
+    fn leap_day_only_in_leap_years() {
+        assert_eq!(
+            dates("FREQ=MONTHLY;BYMONTHDAY=29", "2024-01-29T08:30:00Z", 3),
+            [
+                "2024-01-29T08:30:00+00:00",
+                "2024-02-29T08:30:00+00:00",
+                "2024-03-29T08:30:00+00:00",
+            ]
+        );
+        assert_eq!(
+            dates("FREQ=MONTHLY;BYMONTHDAY=29", "2025-01-29T08:30:00Z", 2),
+            ["2025-01-29T08:30:00+00:00", "2025-03-29T08:30:00+00:00"]
+        );
+        assert_eq!(
+            dates(
+                "FREQ=MONTHLY;INTERVAL=12;BYMONTHDAY=29",
+                "2024-02-29T08:30:00Z",
+                2
+            ),
+            ["2024-02-29T08:30:00+00:00", "2028-02-29T08:30:00+00:00"]
+        );
+    }
+
+    #[test]
+    fn weekly_and_daily_by_weekday() {
+        assert_eq!(
+            dates(
+                "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE",
+                "2025-01-06T10:00:00Z",
+                4
+            ),
+            [
+                "2025-01-06T10:00:00+00:00",
+                "2025-01-08T10:00:00+00:00",
+                "2025-01-20T10:00:00+00:00",
+                "2025-01-22T10:00:00+00:00",
+            ]
+        );
+        assert_eq!(
+            dates("FREQ=DAILY;BYDAY=MO,FR", "2025-01-06T10:00:00Z", 3),
+            [
+                "2025-01-06T10:00:00+00:00",
+                "2025-01-10T10:00:00+00:00",
+                "2025-01-13T10:00:00+00:00",
+            ]
+        );
+    }
+
+    #[test]
+    fn preview_stops_at_count_and_until() {
+        assert_eq!(
+            dates("FREQ=DAILY;COUNT=3", "2025-01-01T10:00:00Z", 10).len(),
+            3
+        );
+        assert_eq!(
+            dates("FREQ=DAILY;UNTIL=20250103", "2025-01-01T10:00:00Z", 10),
+            [
+                "2025-01-01T10:00:00+00:00",
+                "2025-01-02T10:00:00+00:00",
+                "2025-01-03T10:00:00+00:00",
+            ]
+        );
+        assert!(dates("FREQ=DAILY;UNTIL=20241231", "2025-01-01T10:00:00Z", 10).is_empty());
+        assert!(dates("FREQ=DAILY", "2025-01-01T10:00:00Z", 0).is_empty());
+    }
+
+    #[test]
+    fn next_occurrence_respects_count() {
+        let rule: RecurrenceRule = "FREQ=DAILY;COUNT=3".parse().unwrap();
+        let current = at("2025-01-03T10:00:00Z");
+        assert_eq!(
+            rule.next_occurrence(current, 2),
+            Some(at("2025-01-04T10:00:00Z"))
+        );
+        assert_eq!(rule.next_occurrence(current, 3), None);
+    }
+}
@@ -0,0 +1,250 @@
+use actix_web::{get, web, HttpResponse, Responder};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+use sqlx::sqlite::SqlitePool;
+use std::sync::Arc;
+use tokio::sync::Notify;
+use utoipa::IntoParams;
+
+use crate::auth::AuthUser;
+use crate::error::AppError;
+use crate::events::{TaskEventKind, TaskEvents};
+use crate::openapi::Message;
+use crate::status::TaskStatus;
+use crate::{ApiResponse, Task};
+
+// Intervalo máximo entre verificações, mesmo sem lembretes agendados
+const MAX_SLEEP: std::time::Duration = std::time::Duration::from_secs(60);
+// Quantidade de lembretes disparados por rodada
+const BATCH_SIZE: i64 = 100;
+// Janela padrão e máxima de GET /tasks/upcoming, em dias
+const DEFAULT_UPCOMING_DAYS: i64 = 7;
+const MAX_UPCOMING_DAYS: i64 = 365;
+
+// Destino dos lembretes disparados pelo agendador.
+// Novos canais (e-mail, webhook, push) só precisam implementar este contrato.
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    async fn notify(&self, task: &Task) -> Result<(), String>;
+}
+
+// Registra o lembrete no log da aplicação
+pub struct LogNotifier;
+
+#[async_trait]
+impl Notifier for LogNotifier {
+    async fn notify(&self, task: &Task) -> Result<(), String> {
+        log::info!(
+            "Lembrete: tarefa {} ({}) do usuário {}, prazo {}",
+            task.id,
+            task.title,
+            task.owner_id,
+            task.due_at
+                .map(|due_at| due_at.to_rfc3339())
+                .unwrap_or_else(|| "não definido".to_string())
+        );
+        Ok(())
+    }
+}
+
+// Envia o lembrete aos clientes conectados em /tasks/events
+#[async_trait]
+impl Notifier for TaskEvents {
+    async fn notify(&self, task: &Task) -> Result<(), String> {
+        self.publish(TaskEventKind::Reminder, task.clone());
+        Ok(())
+    }
+}
+
+// Agendador de lembretes. Os lembretes pendentes ficam no banco de dados,
+// então sobrevivem a reinicializações: ao subir, os atrasados são disparados.
+pub struct Reminders {
+    wake: Notify,
+}
+
+impl Reminders {
+    pub fn new() -> Self {
+        Reminders {
+            wake: Notify::new(),
+        }
+    }
+
+    // Avisa o agendador que um lembrete foi criado ou alterado
+    pub fn reschedule(&self) {
+        self.wake.notify_one();
+    }
+
+    // Inicia o agendador em segundo plano
+    pub fn spawn(self: Arc<Self>, db: SqlitePool, notifiers: Vec<Arc<dyn Notifier>>) {
+        tokio::spawn(async move {
+            loop {
+                let wait = match fire_due(&db, &notifiers).await {
+                    Ok(Some(next)) => (next - Utc::now()).to_std().unwrap_or_default(),
+                    Ok(None) => MAX_SLEEP,
+                    Err(e) => {
+                        log::error!("Erro ao processar lembretes: {}", e);
+                        MAX_SLEEP
+                    }
+                };
+
+                tokio::select! {
+                    _ = tokio::time::sleep(wait.min(MAX_SLEEP)) => {}
+                    _ = self.wake.notified() => {}
+                }
+            }
+        });
+    }
+}
+
+impl Default for Reminders {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Dispara os lembretes vencidos e retorna o horário do próximo
+async fn fire_due(
+    db: &SqlitePool,
+    notifiers: &[Arc<dyn Notifier>],
+) -> Result<Option<DateTime<Utc>>, sqlx::Error> {
+    let now = Utc::now();
+    let due = sqlx::query_as::<_, Task>(
+        r#"
+        SELECT * FROM tasks
+        WHERE reminded_at IS NULL AND remind_at <= ? AND status NOT IN (?, ?)
+        ORDER BY remind_at
+        LIMIT ?
+        "#,
+    )
+    .bind(now)
+    .bind(TaskStatus::Concluida)
+    .bind(TaskStatus::Cancelada)
+    .bind(BATCH_SIZE)
+    .fetch_all(db)
+    .await?;
+
+    for task in &due {
+        // Marca antes de notificar; a condição em remind_at evita disparar um
+        // lembrete que acabou de ser remarcado pelo usuário
+        let marked = sqlx::query(
+            "UPDATE tasks SET reminded_at = ? WHERE id = ? AND remind_at = ? AND reminded_at IS NULL",
+        )
+        .bind(now)
+        .bind(&task.id)
+        .bind(task.remind_at)
+        .execute(db)
+        .await?;
+        if marked.rows_affected() == 0 {
+            continue;
+        }
+
+        for notifier in notifiers {
+            if let Err(e) = notifier.notify(task).await {
+                log::error!("Erro ao enviar lembrete da tarefa {}: {}", task.id, e);
+            }
+        }
+    }
+
+    // Ainda há lembretes vencidos: processa a próxima rodada em seguida
+    if due.len() as i64 == BATCH_SIZE {
+        return Ok(Some(now));
+    }
+
+    sqlx::query_scalar::<_, Option<DateTime<Utc>>>(
+        "SELECT MIN(remind_at) FROM tasks WHERE reminded_at IS NULL AND remind_at > ? AND status NOT IN (?, ?)",
+    )
+    .bind(now)
+    .bind(TaskStatus::Concluida)
+    .bind(TaskStatus::Cancelada)
+    .fetch_one(db)
+    .await
+}
+
+// Parâmetros de GET /tasks/upcoming
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct UpcomingQuery {
+    days: Option<i64>,
+}
+
+async fn open_tasks_due_between(
+    db: &SqlitePool,
+    owner_id: &str,
+    from: Option<DateTime<Utc>>,
+    to: DateTime<Utc>,
+) -> Result<Vec<Task>, sqlx::Error> {
+    sqlx::query_as::<_, Task>(
+        r#"
+        SELECT * FROM tasks
+        WHERE owner_id = ? AND due_at IS NOT NULL AND (? IS NULL OR due_at >= ?) AND due_at < ?
+          AND status NOT IN (?, ?)
+        ORDER BY due_at, id
+        "#,
+    )
+    .bind(owner_id)
+    .bind(from)
+    .bind(from)
+    .bind(to)
+    .bind(TaskStatus::Concluida)
+    .bind(TaskStatus::Cancelada)
+    .fetch_all(db)
+    .await
+}
+
+// Handler para listar as tarefas em aberto com prazo vencido
+#[utoipa::path(
+    tag = "lembretes",
+    summary = "Lista as tarefas em aberto com prazo vencido",
+    responses((status = 200, body = ApiResponse<Vec<Task>>))
+)]
+#[get("/tasks/overdue")]
+pub async fn get_overdue(
+    db: web::Data<SqlitePool>,
+    user: AuthUser,
+) -> Result<impl Responder, AppError> {
+    let tasks = open_tasks_due_between(db.get_ref(), &user.id, None, Utc::now()).await?;
+    Ok(HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        message: "Tarefas atrasadas recuperadas com sucesso".to_string(),
+        data: Some(tasks),
+    }))
+}
+
+// Handler para listar as tarefas em aberto com prazo nos próximos `days` dias
+#[utoipa::path(
+    tag = "lembretes",
+    summary = "Lista as tarefas em aberto com prazo nos próximos dias",
+    params(UpcomingQuery),
+    responses(
+        (status = 200, body = ApiResponse<Vec<Task>>),
+        (status = 400, description = "days fora do intervalo permitido", body = Message),
+    )
+)]
+#[get("/tasks/upcoming")]
+pub async fn get_upcoming(
+    db: web::Data<SqlitePool>,
+    user: AuthUser,
+    query: web::Query<UpcomingQuery>,
+) -> Result<impl Responder, AppError> {
+    let days = query.days.unwrap_or(DEFAULT_UPCOMING_DAYS);
+    if !(1..=MAX_UPCOMING_DAYS).contains(&days) {
+        return Err(AppError::BadRequest(format!(
+            "days deve estar entre 1 e {}",
+            MAX_UPCOMING_DAYS
+        )));
+    }
+
+    let now = Utc::now();
+    let tasks = open_tasks_due_between(
+        db.get_ref(),
+        &user.id,
+        Some(now),
+        now + Duration::days(days),
+    )
+    .await?;
+    Ok(HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        message: "Próximas tarefas recuperadas com sucesso".to_string(),
+        data: Some(tasks),
+    }))
+}
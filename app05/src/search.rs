@@ -0,0 +1,135 @@
+use actix_web::{get, web, HttpResponse, Responder};
+use serde::{Deserialize, Serialize};
+use sqlx::{sqlite::SqlitePool, FromRow};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::auth::AuthUser;
+use crate::error::AppError;
+use crate::labels::LABELS;
+use crate::openapi::Message;
+use crate::subtasks::PROGRESS;
+use crate::{ApiResponse, Task};
+
+// Quantidade padrão e máxima de resultados
+const DEFAULT_LIMIT: i64 = 20;
+const MAX_LIMIT: i64 = 100;
+// Pesos do bm25 para título, descrição e comentários
+const WEIGHTS: &str = "10.0, 4.0, 1.0";
+// Marcadores usados pelo SQLite em volta dos termos encontrados; viram <mark> depois
+// que o texto é escapado, para que o conteúdo da tarefa nunca seja interpretado como HTML
+const MARK_START: char = '\u{2}';
+const MARK_END: char = '\u{3}';
+
+// Parâmetros de GET /tasks/search
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SearchQuery {
+    q: Option<String>,
+    limit: Option<i64>,
+}
+
+// Resultado da busca: a tarefa, a relevância e os trechos com os termos destacados
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct SearchHit {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub task: Task,
+    // Quanto maior, mais relevante
+    pub score: f64,
+    // Título com os termos entre <mark> e </mark>
+    pub title_highlight: String,
+    // Trecho mais relevante entre título, descrição e comentários
+    pub snippet: String,
+}
+
+// Converte o texto digitado em uma consulta FTS5: cada palavra vira um termo entre aspas
+// com busca por prefixo, e todas precisam aparecer. Assim a sintaxe do FTS5 (aspas,
+// operadores, parênteses) nunca chega crua ao MATCH.
+fn match_expression(q: &str) -> Option<String> {
+    let terms: Vec<String> = q
+        .split_whitespace()
+        .map(|term| term.replace('"', ""))
+        .filter(|term| term.chars().any(char::is_alphanumeric))
+        .map(|term| format!("\"{}\"*", term))
+        .collect();
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+// Escapa o HTML do trecho e troca os marcadores do SQLite por <mark>
+fn mark(text: &str) -> String {
+    let mut html = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            MARK_START => html.push_str("<mark>"),
+            MARK_END => html.push_str("</mark>"),
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            '\'' => html.push_str("&#39;"),
+            c => html.push(c),
+        }
+    }
+    html
+}
+
+// Handler para buscar tarefas por texto, ignorando maiúsculas e acentos
+#[utoipa::path(
+    tag = "tarefas",
+    summary = "Busca tarefas por texto no título, na descrição e nos comentários",
+    params(SearchQuery),
+    responses(
+        (status = 200, body = ApiResponse<Vec<SearchHit>>),
+        (status = 400, description = "Texto da busca ausente", body = Message),
+    )
+)]
+#[get("/tasks/search")]
+pub async fn search_tasks(
+    db: web::Data<SqlitePool>,
+    user: AuthUser,
+    query: web::Query<SearchQuery>,
+) -> Result<impl Responder, AppError> {
+    let Some(expression) = query.q.as_deref().and_then(match_expression) else {
+        return Err(AppError::BadRequest(
+            "Informe o texto da busca em q".to_string(),
+        ));
+    };
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    let sql = format!(
+        r#"
+        SELECT tasks.*, {progress}, {labels},
+               -bm25(tasks_fts, {weights}) AS score,
+               highlight(tasks_fts, 0, char(2), char(3)) AS title_highlight,
+               snippet(tasks_fts, -1, char(2), char(3), '…', 16) AS snippet
+        FROM tasks_fts
+        JOIN task_search_docs d ON d.doc_id = tasks_fts.rowid
+        JOIN tasks ON tasks.id = d.task_id
+        WHERE tasks_fts MATCH ? AND tasks.owner_id = ?
+        ORDER BY bm25(tasks_fts, {weights})
+        LIMIT ?
+        "#,
+        progress = PROGRESS,
+        labels = LABELS,
+        weights = WEIGHTS
+    );
+
+    let mut hits = sqlx::query_as::<_, SearchHit>(&sql)
+        .bind(&expression)
+        .bind(&user.id)
+        .bind(limit)
+        .fetch_all(db.get_ref())
+        .await?;
+    for hit in &mut hits {
+        hit.title_highlight = mark(&hit.title_highlight);
+        hit.snippet = mark(&hit.snippet);
+    }
+    Ok(HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        message: format!("{} tarefa(s) encontrada(s)", hits.len()),
+        data: Some(hits),
+    }))
+}
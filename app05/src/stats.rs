@@ -0,0 +1,331 @@
+use actix_web::{get, web, HttpResponse, Responder};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{
+    sqlite::{SqliteConnection, SqlitePool},
+    FromRow,
+};
+use std::collections::HashMap;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::auth::AuthUser;
+use crate::error::AppError;
+use crate::openapi::Message;
+use crate::status::TaskStatus;
+use crate::ApiResponse;
+
+// Intervalo padrão da série temporal
+const DEFAULT_RANGE_DAYS: i64 = 30;
+// Quantidade máxima de períodos na série temporal
+const MAX_PERIODS: i64 = 400;
+
+// Agrupamento da série temporal
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Bucket {
+    #[default]
+    Day,
+    // Semanas de segunda a domingo, identificadas pela segunda-feira
+    Week,
+}
+
+impl Bucket {
+    // Expressão SQL com a data (YYYY-MM-DD) do início do período da coluna
+    fn period(self, column: &str) -> String {
+        match self {
+            Bucket::Day => format!("date({})", column),
+            Bucket::Week => format!("date({}, '-6 days', 'weekday 1')", column),
+        }
+    }
+
+    // Início do período que contém `date`
+    fn start(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Bucket::Day => date,
+            Bucket::Week => date - Duration::days(date.weekday().num_days_from_monday() as i64),
+        }
+    }
+
+    fn length(self) -> Duration {
+        match self {
+            Bucket::Day => Duration::days(1),
+            Bucket::Week => Duration::weeks(1),
+        }
+    }
+}
+
+// Parâmetros de GET /tasks/stats
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct StatsQuery {
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    bucket: Option<Bucket>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StatusCount {
+    pub status: TaskStatus,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct PriorityCount {
+    pub priority: i32,
+    pub count: i64,
+}
+
+// Tarefas criadas e concluídas em um período
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TimelinePoint {
+    // Data de início do período (YYYY-MM-DD, em UTC)
+    pub period: String,
+    pub created: i64,
+    pub completed: i64,
+}
+
+// Tempo entre a criação e a conclusão das tarefas concluídas no intervalo
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct LeadTime {
+    pub completed: i64,
+    pub average_hours: Option<f64>,
+}
+
+// Tarefas em aberto com prazo vencido
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OverdueCount {
+    pub total: i64,
+    pub by_priority: Vec<PriorityCount>,
+}
+
+// Resposta de GET /tasks/stats. Os totais por status e prioridade e os atrasos consideram
+// todas as tarefas; a série temporal e o tempo de entrega, só o intervalo [from, to).
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TaskStats {
+    pub total: i64,
+    pub by_status: Vec<StatusCount>,
+    pub by_priority: Vec<PriorityCount>,
+    pub overdue: OverdueCount,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub bucket: Bucket,
+    pub timeline: Vec<TimelinePoint>,
+    pub lead_time: LeadTime,
+}
+
+// Quantidade de tarefas por período de `column` dentro do intervalo
+async fn count_by_period(
+    conn: &mut SqliteConnection,
+    owner_id: &str,
+    column: &str,
+    bucket: Bucket,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<HashMap<String, i64>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, (String, i64)>(&format!(
+        r#"
+        SELECT {period} AS period, COUNT(*) FROM tasks
+        WHERE owner_id = ? AND {column} >= ? AND {column} < ?
+        GROUP BY period
+        "#,
+        period = bucket.period(column),
+        column = column
+    ))
+    .bind(owner_id)
+    .bind(from)
+    .bind(to)
+    .fetch_all(conn)
+    .await?;
+    Ok(rows.into_iter().collect())
+}
+
+async fn compute(
+    conn: &mut SqliteConnection,
+    owner_id: &str,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    bucket: Bucket,
+) -> Result<TaskStats, sqlx::Error> {
+    let status_counts: HashMap<TaskStatus, i64> = sqlx::query_as::<_, (TaskStatus, i64)>(
+        "SELECT status, COUNT(*) FROM tasks WHERE owner_id = ? GROUP BY status",
+    )
+    .bind(owner_id)
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .collect();
+    // Todos os status aparecem, mesmo sem tarefas
+    let by_status: Vec<StatusCount> = TaskStatus::ALL
+        .into_iter()
+        .map(|status| StatusCount {
+            status,
+            count: status_counts.get(&status).copied().unwrap_or(0),
+        })
+        .collect();
+
+    let by_priority = sqlx::query_as::<_, PriorityCount>(
+        r#"
+        SELECT priority, COUNT(*) AS count FROM tasks
+        WHERE owner_id = ?
+        GROUP BY priority ORDER BY priority
+        "#,
+    )
+    .bind(owner_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    // Mesmo critério de GET /tasks/overdue
+    let overdue = sqlx::query_as::<_, PriorityCount>(
+        r#"
+        SELECT priority, COUNT(*) AS count FROM tasks
+        WHERE owner_id = ? AND due_at IS NOT NULL AND due_at < ? AND status NOT IN (?, ?)
+        GROUP BY priority ORDER BY priority
+        "#,
+    )
+    .bind(owner_id)
+    .bind(Utc::now())
+    .bind(TaskStatus::Concluida)
+    .bind(TaskStatus::Cancelada)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let created = count_by_period(conn, owner_id, "created_at", bucket, from, to).await?;
+    let completed = count_by_period(conn, owner_id, "completed_at", bucket, from, to).await?;
+
+    let lead_time = sqlx::query_as::<_, LeadTime>(
+        r#"
+        SELECT COUNT(*) AS completed,
+               AVG((julianday(completed_at) - julianday(created_at)) * 24.0) AS average_hours
+        FROM tasks
+        WHERE owner_id = ? AND completed_at >= ? AND completed_at < ?
+        "#,
+    )
+    .bind(owner_id)
+    .bind(from)
+    .bind(to)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    // Períodos sem tarefas aparecem com zero
+    let mut timeline = Vec::new();
+    let mut period = bucket.start(from.date_naive());
+    while period.and_hms_opt(0, 0, 0).map(|start| start.and_utc()) < Some(to) {
+        let key = period.format("%Y-%m-%d").to_string();
+        timeline.push(TimelinePoint {
+            created: created.get(&key).copied().unwrap_or(0),
+            completed: completed.get(&key).copied().unwrap_or(0),
+            period: key,
+        });
+        period += bucket.length();
+    }
+
+    Ok(TaskStats {
+        total: by_status.iter().map(|entry| entry.count).sum(),
+        by_status,
+        by_priority,
+        overdue: OverdueCount {
+            total: overdue.iter().map(|entry| entry.count).sum(),
+            by_priority: overdue,
+        },
+        from,
+        to,
+        bucket,
+        timeline,
+        lead_time,
+    })
+}
+
+// Handler para as estatísticas das tarefas do usuário
+#[utoipa::path(
+    tag = "tarefas",
+    summary = "Calcula estatísticas das tarefas do usuário",
+    params(StatsQuery),
+    responses(
+        (status = 200, body = ApiResponse<TaskStats>),
+        (status = 400, description = "Intervalo inválido ou com períodos demais", body = Message),
+    )
+)]
+#[get("/tasks/stats")]
+pub async fn get_stats(
+    db: web::Data<SqlitePool>,
+    user: AuthUser,
+    query: web::Query<StatsQuery>,
+) -> Result<impl Responder, AppError> {
+    let to = query.to.unwrap_or_else(Utc::now);
+    let from = query
+        .from
+        .unwrap_or_else(|| to - Duration::days(DEFAULT_RANGE_DAYS));
+    let bucket = query.bucket.unwrap_or_default();
+
+    let periods = (to - from).num_seconds() / bucket.length().num_seconds();
+    if from >= to {
+        return Err(AppError::BadRequest(
+            "from deve ser anterior a to".to_string(),
+        ));
+    }
+    if periods > MAX_PERIODS {
+        return Err(AppError::BadRequest(format!(
+            "O intervalo deve ter no máximo {} períodos; reduza o intervalo ou use bucket=week",
+            MAX_PERIODS
+        )));
+    }
+
+    // Todas as contagens na mesma transação veem o mesmo estado do banco
+    let mut tx = db.begin().await?;
+    let stats = compute(&mut tx, &user.id, from, to, bucket).await?;
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        message: "Estatísticas calculadas com sucesso".to_string(),
+        data: Some(stats),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Início do período calculado pelo SQLite para o instante `at`
+    async fn sqlite_start(db: &SqlitePool, bucket: Bucket, at: &str) -> NaiveDate {
+        let period: String = sqlx::query_scalar(&format!("SELECT {}", bucket.period("?")))
+            .bind(at)
+            .fetch_one(db)
+            .await
+            .unwrap();
+        period.parse().unwrap()
+    }
+
+    #[actix_web::test]
+    async fn week_start_matches_sqlite_across_week_boundaries() {
+        let db = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        // De sexta, 8/3, a terça, 19/3: passa por duas viradas de semana
+        let first = NaiveDate::from_ymd_opt(2024, 3, 8).unwrap();
+        for offset in 0..12 {
+            let date = first + Duration::days(offset);
+            for time in ["00:00:00Z", "23:59:59.999Z"] {
+                let at = format!("{}T{}", date, time);
+                let expected = Bucket::Week.start(date);
+                assert_eq!(expected.weekday(), chrono::Weekday::Mon);
+                assert_eq!(
+                    sqlite_start(&db, Bucket::Week, &at).await,
+                    expected,
+                    "{}",
+                    at
+                );
+                assert_eq!(sqlite_start(&db, Bucket::Day, &at).await, date, "{}", at);
+            }
+        }
+    }
+
+    #[test]
+    fn week_start_is_the_monday() {
+        let sunday = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+        let monday = NaiveDate::from_ymd_opt(2024, 3, 11).unwrap();
+        assert_eq!(
+            Bucket::Week.start(sunday),
+            NaiveDate::from_ymd_opt(2024, 3, 4).unwrap()
+        );
+        assert_eq!(Bucket::Week.start(monday), monday);
+        assert_eq!(Bucket::Day.start(sunday), sunday);
+    }
+}
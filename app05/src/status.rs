@@ -0,0 +1,160 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+// Status possíveis de uma tarefa (os mesmos rótulos usados pelo frontend)
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    sqlx::Type,
+    TS,
+    ToSchema,
+)]
+#[ts(repr(enum = name))]
+pub enum TaskStatus {
+    #[default]
+    #[serde(rename = "Pendente")]
+    #[sqlx(rename = "Pendente")]
+    Pendente,
+    #[serde(rename = "Em Andamento")]
+    #[sqlx(rename = "Em Andamento")]
+    EmAndamento,
+    #[serde(rename = "Concluída")]
+    #[sqlx(rename = "Concluída")]
+    Concluida,
+    #[serde(rename = "Cancelada")]
+    #[sqlx(rename = "Cancelada")]
+    Cancelada,
+}
+
+impl TaskStatus {
+    // Todos os status, na ordem do fluxo
+    pub const ALL: [TaskStatus; 4] = [
+        TaskStatus::Pendente,
+        TaskStatus::EmAndamento,
+        TaskStatus::Concluida,
+        TaskStatus::Cancelada,
+    ];
+
+    // Rótulo do status, igual ao valor gravado no banco e enviado no JSON
+    pub fn label(self) -> &'static str {
+        match self {
+            TaskStatus::Pendente => "Pendente",
+            TaskStatus::EmAndamento => "Em Andamento",
+            TaskStatus::Concluida => "Concluída",
+            TaskStatus::Cancelada => "Cancelada",
+        }
+    }
+
+    // Estados para os quais a tarefa pode ir a partir do estado atual
+    pub fn allowed_transitions(self) -> &'static [TaskStatus] {
+        match self {
+            TaskStatus::Pendente => &[
+                TaskStatus::EmAndamento,
+                TaskStatus::Concluida,
+                TaskStatus::Cancelada,
+            ],
+            TaskStatus::EmAndamento => &[
+                TaskStatus::Pendente,
+                TaskStatus::Concluida,
+                TaskStatus::Cancelada,
+            ],
+            // Uma tarefa concluída só pode ser reaberta
+            TaskStatus::Concluida => &[TaskStatus::EmAndamento],
+            // Uma tarefa cancelada precisa voltar para Pendente antes de seguir
+            TaskStatus::Cancelada => &[TaskStatus::Pendente],
+        }
+    }
+
+    // Verifica se a transição é permitida (manter o mesmo status é sempre válido)
+    pub fn can_transition_to(self, next: TaskStatus) -> bool {
+        self == next || self.allowed_transitions().contains(&next)
+    }
+}
+
+impl fmt::Display for TaskStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl FromStr for TaskStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Pendente" => Ok(TaskStatus::Pendente),
+            "Em Andamento" => Ok(TaskStatus::EmAndamento),
+            "Concluída" => Ok(TaskStatus::Concluida),
+            "Cancelada" => Ok(TaskStatus::Cancelada),
+            other => Err(format!("Status desconhecido: {}", other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeping_the_same_status_is_always_allowed() {
+        for status in TaskStatus::ALL {
+            assert!(status.can_transition_to(status), "{} -> {}", status, status);
+        }
+    }
+
+    #[test]
+    fn transitions_follow_the_graph() {
+        use TaskStatus::*;
+        let allowed = [
+            (Pendente, EmAndamento),
+            (Pendente, Concluida),
+            (Pendente, Cancelada),
+            (EmAndamento, Pendente),
+            (EmAndamento, Concluida),
+            (EmAndamento, Cancelada),
+            (Concluida, EmAndamento),
+            (Cancelada, Pendente),
+        ];
+        for from in TaskStatus::ALL {
+            for to in TaskStatus::ALL.into_iter().filter(|to| *to != from) {
+                assert_eq!(
+                    from.can_transition_to(to),
+                    allowed.contains(&(from, to)),
+                    "{} -> {}",
+                    from,
+                    to
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn finished_tasks_must_be_reopened_first() {
+        assert!(!TaskStatus::Concluida.can_transition_to(TaskStatus::Pendente));
+        assert!(!TaskStatus::Concluida.can_transition_to(TaskStatus::Cancelada));
+        assert!(!TaskStatus::Cancelada.can_transition_to(TaskStatus::EmAndamento));
+        assert!(!TaskStatus::Cancelada.can_transition_to(TaskStatus::Concluida));
+    }
+
+    #[test]
+    fn labels_round_trip() {
+        for status in TaskStatus::ALL {
+            let json = serde_json::to_string(&status).unwrap();
+            assert_eq!(json, format!("\"{}\"", status));
+            assert_eq!(serde_json::from_str::<TaskStatus>(&json).unwrap(), status);
+            assert_eq!(status.label().parse::<TaskStatus>(), Ok(status));
+        }
+        assert!(serde_json::from_str::<TaskStatus>("\"Feito\"").is_err());
+        assert!("Feito".parse::<TaskStatus>().is_err());
+    }
+}
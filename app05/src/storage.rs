@@ -0,0 +1,75 @@
+use actix_web::web::Bytes;
+use async_trait::async_trait;
+use std::io;
+use std::path::PathBuf;
+use tokio::fs;
+use uuid::Uuid;
+
+// Armazenamento de conteúdo endereçado pela chave (o hash SHA-256 do arquivo).
+// Outras implementações (S3, banco de dados, memória) só precisam deste contrato.
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+    // Grava o conteúdo; se a chave já existir, o conteúdo é idêntico e nada é feito
+    async fn put(&self, key: &str, data: Bytes) -> io::Result<()>;
+    async fn get(&self, key: &str) -> io::Result<Option<Bytes>>;
+    // Remove o conteúdo; chaves inexistentes são ignoradas
+    async fn delete(&self, key: &str) -> io::Result<()>;
+}
+
+// Armazena os arquivos no disco em <raiz>/<2 primeiros caracteres>/<hash>
+pub struct LocalStore {
+    root: PathBuf,
+}
+
+impl LocalStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        LocalStore { root: root.into() }
+    }
+
+    fn path(&self, key: &str) -> io::Result<PathBuf> {
+        // A chave vira caminho no disco: aceita apenas hashes hexadecimais
+        if key.len() < 3 || !key.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Chave de armazenamento inválida: {}", key),
+            ));
+        }
+        Ok(self.root.join(&key[..2]).join(key))
+    }
+}
+
+#[async_trait]
+impl BlobStore for LocalStore {
+    async fn put(&self, key: &str, data: Bytes) -> io::Result<()> {
+        let path = self.path(key)?;
+        if fs::try_exists(&path).await? {
+            return Ok(());
+        }
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).await?;
+        }
+
+        // Grava em um arquivo temporário e renomeia para nunca expor conteúdo pela metade
+        let tmp = path.with_extension(format!("{}.tmp", Uuid::new_v4()));
+        if let Err(e) = fs::write(&tmp, &data).await {
+            let _ = fs::remove_file(&tmp).await;
+            return Err(e);
+        }
+        fs::rename(&tmp, &path).await
+    }
+
+    async fn get(&self, key: &str) -> io::Result<Option<Bytes>> {
+        match fs::read(self.path(key)?).await {
+            Ok(data) => Ok(Some(Bytes::from(data))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> io::Result<()> {
+        match fs::remove_file(self.path(key)?).await {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+}
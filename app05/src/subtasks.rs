@@ -0,0 +1,56 @@
+use sqlx::sqlite::SqliteConnection;
+
+use crate::find_task;
+
+// Percentual (0 a 100) de subtarefas diretas concluídas, sem contar as canceladas.
+// Fica nulo quando a tarefa não tem subtarefas. Usada nas listagens de `tasks`.
+pub const PROGRESS: &str = r#"
+    (SELECT CAST(ROUND(100.0 * SUM(s.status = 'Concluída') / COUNT(*)) AS INTEGER)
+     FROM tasks s
+     WHERE s.parent_id = tasks.id AND s.status <> 'Cancelada') AS progress
+"#;
+
+// Passo recursivo de uma CTE `subtree (id)`: a partir das tarefas iniciais,
+// inclui todas as subtarefas em qualquer nível
+pub const SUBTREE_STEP: &str =
+    " UNION SELECT t.id FROM tasks t JOIN subtree s ON t.parent_id = s.id";
+
+// Verifica se `parent_id` pode ser a tarefa pai de `task_id`: precisa existir, ser do
+// mesmo usuário e não pode ser a própria tarefa nem uma de suas subtarefas
+pub async fn check_parent(
+    conn: &mut SqliteConnection,
+    task_id: &str,
+    parent_id: &str,
+    owner_id: &str,
+) -> Result<Result<(), String>, sqlx::Error> {
+    if parent_id == task_id {
+        return Ok(Err(
+            "Uma tarefa não pode ser subtarefa de si mesma".to_string()
+        ));
+    }
+    if find_task(&mut *conn, parent_id, owner_id).await?.is_none() {
+        return Ok(Err(format!(
+            "Tarefa pai com ID {} não encontrada",
+            parent_id
+        )));
+    }
+
+    let descendant: bool = sqlx::query_scalar(&format!(
+        r#"
+        WITH RECURSIVE subtree (id) AS (SELECT ?{})
+        SELECT EXISTS (SELECT 1 FROM subtree WHERE id = ?)
+        "#,
+        SUBTREE_STEP
+    ))
+    .bind(task_id)
+    .bind(parent_id)
+    .fetch_one(&mut *conn)
+    .await?;
+    if descendant {
+        return Ok(Err(format!(
+            "A tarefa {} é subtarefa de {}; a alteração criaria um ciclo",
+            parent_id, task_id
+        )));
+    }
+    Ok(Ok(()))
+}
@@ -0,0 +1,667 @@
+use actix_web::{get, http::StatusCode, post, web, HttpResponse, Responder};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{
+    sqlite::{SqliteConnection, SqlitePool},
+    Connection, FromRow,
+};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::attachments::Attachments;
+use crate::auth::AuthUser;
+use crate::batch::Effects;
+use crate::error::AppError;
+use crate::events::TaskEvents;
+use crate::labels::LABELS;
+use crate::openapi::Message;
+use crate::reminders::Reminders;
+use crate::subtasks::PROGRESS;
+use crate::validation;
+use crate::{
+    apply_update, check_new_task, delete_task_tree, find_task, insert_task, prepare_new_task,
+    save_update, ApiResponse, Task, TaskUpdate, UpdateRejection,
+};
+
+// Quantidade padrão e máxima de alterações por página
+const DEFAULT_LIMIT: i64 = 200;
+const MAX_LIMIT: i64 = 1000;
+// Quantidade máxima de alterações enviadas pelo cliente de uma vez
+const MAX_PUSH_SIZE: usize = 500;
+
+// Parâmetros de GET /sync
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SyncQuery {
+    // Cursor devolvido pela sincronização anterior; ausente na primeira
+    since: Option<String>,
+    limit: Option<i64>,
+}
+
+// Registro de task_changes
+#[derive(Debug, FromRow)]
+struct Change {
+    seq: i64,
+    task_id: String,
+    deleted_at: Option<DateTime<Utc>>,
+}
+
+// Tarefa excluída desde o cursor
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Tombstone {
+    pub id: String,
+    pub deleted_at: DateTime<Utc>,
+}
+
+// Alterações desde o cursor; o próximo pedido deve enviar `cursor` em `since`
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SyncPage {
+    pub tasks: Vec<Task>,
+    pub tombstones: Vec<Tombstone>,
+    pub cursor: String,
+    // Há mais alterações além desta página
+    pub has_more: bool,
+}
+
+// Alteração feita no cliente, identificada pelo campo `op`. Alterações e exclusões
+// informam a versão (ou o updated_at) que o cliente tinha quando editou a tarefa.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum SyncChange {
+    // A tarefa pode trazer um UUID gerado no cliente, para que ele reconheça a tarefa
+    // criada offline e para que o reenvio não a duplique
+    Create {
+        task: Task,
+    },
+    Update {
+        id: String,
+        #[serde(default)]
+        base_version: Option<i64>,
+        #[serde(default)]
+        base_updated_at: Option<DateTime<Utc>>,
+        changes: TaskUpdate,
+    },
+    Delete {
+        id: String,
+        #[serde(default)]
+        base_version: Option<i64>,
+        #[serde(default)]
+        base_updated_at: Option<DateTime<Utc>>,
+    },
+}
+
+impl SyncChange {
+    fn name(&self) -> &'static str {
+        match self {
+            SyncChange::Create { .. } => "create",
+            SyncChange::Update { .. } => "update",
+            SyncChange::Delete { .. } => "delete",
+        }
+    }
+}
+
+// Corpo de POST /sync
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SyncPush {
+    changes: Vec<SyncChange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncOutcome {
+    // Gravada no servidor
+    Applied,
+    // A tarefa mudou ou foi excluída no servidor depois da versão que o cliente tinha
+    Conflict,
+    // Recusada pela validação, como na rota individual
+    Rejected,
+}
+
+// Resultado de uma alteração enviada, na mesma ordem do pedido
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SyncResult {
+    pub index: usize,
+    pub op: &'static str,
+    pub id: String,
+    pub outcome: SyncOutcome,
+    // Código HTTP que a alteração teria na rota individual
+    pub status: u16,
+    pub message: String,
+    // Cópia atual da tarefa no servidor, quando ela existe
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task: Option<Task>,
+    // A tarefa foi excluída no servidor
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub deleted: bool,
+}
+
+// Resultado de uma alteração antes de receber índice e operação
+struct Resolution {
+    outcome: SyncOutcome,
+    status: StatusCode,
+    message: String,
+    task: Option<Task>,
+    deleted: bool,
+}
+
+impl Resolution {
+    fn applied(status: StatusCode, message: &str, task: Option<Task>) -> Self {
+        Resolution {
+            outcome: SyncOutcome::Applied,
+            status,
+            message: message.to_string(),
+            task,
+            deleted: false,
+        }
+    }
+
+    fn rejected(status: StatusCode, message: String) -> Self {
+        Resolution {
+            outcome: SyncOutcome::Rejected,
+            status,
+            message,
+            task: None,
+            deleted: false,
+        }
+    }
+
+    fn conflict(task: Task) -> Self {
+        Resolution {
+            outcome: SyncOutcome::Conflict,
+            status: StatusCode::CONFLICT,
+            message: format!("A tarefa com ID {} foi alterada no servidor", task.id),
+            task: Some(task),
+            deleted: false,
+        }
+    }
+
+    fn exists(task: Task) -> Self {
+        Resolution {
+            message: format!("A tarefa com ID {} já existe no servidor", task.id),
+            ..Self::conflict(task)
+        }
+    }
+
+    fn deleted(id: &str) -> Self {
+        Resolution {
+            outcome: SyncOutcome::Conflict,
+            status: StatusCode::GONE,
+            message: format!("A tarefa com ID {} foi excluída no servidor", id),
+            task: None,
+            deleted: true,
+        }
+    }
+}
+
+// Lê o cursor enviado em `since`
+fn parse_cursor(since: Option<&str>) -> Result<i64, String> {
+    match since {
+        None => Ok(0),
+        Some(cursor) => cursor
+            .parse::<i64>()
+            .ok()
+            .filter(|seq| *seq >= 0)
+            .ok_or_else(|| format!("Cursor inválido: {}", cursor)),
+    }
+}
+
+// Verifica se a tarefa já foi excluída (tem um tombstone)
+async fn is_deleted(
+    conn: &mut SqliteConnection,
+    id: &str,
+    owner_id: &str,
+) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar(
+        r#"
+        SELECT EXISTS (SELECT 1 FROM task_changes
+                       WHERE task_id = ? AND owner_id = ? AND deleted_at IS NOT NULL)
+        "#,
+    )
+    .bind(id)
+    .bind(owner_id)
+    .fetch_one(conn)
+    .await
+}
+
+// Verifica se o ID já foi de uma tarefa excluída, de qualquer usuário. O ID de uma
+// tarefa excluída não é reaproveitado, para que o tombstone continue valendo.
+async fn has_tombstone(conn: &mut SqliteConnection, id: &str) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM task_changes WHERE task_id = ? AND deleted_at IS NOT NULL)",
+    )
+    .bind(id)
+    .fetch_one(conn)
+    .await
+}
+
+// A versão que o cliente tinha ainda é a atual? Sem nenhuma das duas, a alteração é recusada.
+fn check_base(
+    task: &Task,
+    base_version: Option<i64>,
+    base_updated_at: Option<DateTime<Utc>>,
+) -> Result<bool, String> {
+    match (base_version, base_updated_at) {
+        (None, None) => Err("Informe base_version ou base_updated_at".to_string()),
+        (version, updated_at) => Ok(version.is_none_or(|v| v == task.version)
+            && updated_at.is_none_or(|at| at == task.updated_at)),
+    }
+}
+
+// Aplica uma alteração do cliente na conexão recebida (um savepoint da sincronização)
+async fn apply_change(
+    conn: &mut SqliteConnection,
+    owner_id: &str,
+    change: SyncChange,
+    effects: &mut Effects,
+) -> Result<(String, Resolution), sqlx::Error> {
+    match change {
+        SyncChange::Create { task } => {
+            let id = if task.id.is_empty() {
+                Uuid::new_v4().to_string()
+            } else {
+                task.id.clone()
+            };
+            if Uuid::parse_str(&id).is_err() {
+                let message = format!("O ID {} não é um UUID válido", id);
+                return Ok((
+                    id,
+                    Resolution::rejected(StatusCode::UNPROCESSABLE_ENTITY, message),
+                ));
+            }
+            // Reenvio de uma criação que já chegou ao servidor
+            if let Some(existing) = find_task(&mut *conn, &id, owner_id).await? {
+                return Ok((id, Resolution::exists(existing)));
+            }
+            if is_deleted(conn, &id, owner_id).await? {
+                return Ok((id.clone(), Resolution::deleted(&id)));
+            }
+            if has_tombstone(conn, &id).await? {
+                let message = format!("O ID {} já está em uso", id);
+                return Ok((id, Resolution::rejected(StatusCode::CONFLICT, message)));
+            }
+
+            // As alterações não passam pelo ValidJson: as regras dos campos valem aqui
+            let prepared = task
+                .validate()
+                .map_err(|errors| validation::summary(&errors))
+                .and_then(|_| prepare_new_task(task, id.clone(), owner_id.to_string()));
+            let mut new_task = match prepared {
+                Ok(new_task) => new_task,
+                Err(message) => {
+                    return Ok((
+                        id,
+                        Resolution::rejected(StatusCode::UNPROCESSABLE_ENTITY, message),
+                    ))
+                }
+            };
+            if let Err(message) = check_new_task(conn, &mut new_task).await? {
+                return Ok((
+                    id,
+                    Resolution::rejected(StatusCode::UNPROCESSABLE_ENTITY, message),
+                ));
+            }
+            // O ID pode pertencer a uma tarefa de outro usuário
+            if let Err(e) = insert_task(conn, &new_task).await {
+                let duplicate = e
+                    .as_database_error()
+                    .map(|db_err| db_err.is_unique_violation())
+                    .unwrap_or(false);
+                if duplicate {
+                    let message = format!("O ID {} já está em uso", id);
+                    return Ok((id, Resolution::rejected(StatusCode::CONFLICT, message)));
+                }
+                return Err(e);
+            }
+            effects.created(new_task.clone());
+            let resolution = Resolution::applied(
+                StatusCode::CREATED,
+                "Tarefa criada com sucesso",
+                Some(new_task),
+            );
+            Ok((id, resolution))
+        }
+        SyncChange::Update {
+            id,
+            base_version,
+            base_updated_at,
+            changes,
+        } => {
+            let Some(existing) = find_task(&mut *conn, &id, owner_id).await? else {
+                return Ok((id.clone(), missing(conn, &id, owner_id).await?));
+            };
+            match check_base(&existing, base_version, base_updated_at) {
+                Ok(true) => {}
+                Ok(false) => return Ok((id, Resolution::conflict(existing))),
+                Err(message) => {
+                    return Ok((
+                        id,
+                        Resolution::rejected(StatusCode::UNPROCESSABLE_ENTITY, message),
+                    ))
+                }
+            }
+            let target = match changes
+                .validate()
+                .map_err(|errors| validation::summary(&errors))
+                .and_then(|_| apply_update(&existing, &changes))
+            {
+                Ok(target) => target,
+                Err(message) => {
+                    return Ok((
+                        id,
+                        Resolution::rejected(StatusCode::UNPROCESSABLE_ENTITY, message),
+                    ))
+                }
+            };
+            let resolution = match save_update(conn, owner_id, &existing, &target).await? {
+                Ok((updated, next)) => {
+                    effects.updated(&existing, updated.clone(), next);
+                    Resolution::applied(
+                        StatusCode::OK,
+                        "Tarefa atualizada com sucesso",
+                        Some(updated),
+                    )
+                }
+                Err(UpdateRejection::Stale) => Resolution::conflict(existing),
+                Err(UpdateRejection::Blocked(blockers)) => Resolution::rejected(
+                    StatusCode::CONFLICT,
+                    format!(
+                        "A tarefa depende de {} tarefa(s) não finalizada(s)",
+                        blockers.len()
+                    ),
+                ),
+                Err(UpdateRejection::Invalid(message)) => {
+                    Resolution::rejected(StatusCode::UNPROCESSABLE_ENTITY, message)
+                }
+            };
+            Ok((id, resolution))
+        }
+        SyncChange::Delete {
+            id,
+            base_version,
+            base_updated_at,
+        } => {
+            let Some(existing) = find_task(&mut *conn, &id, owner_id).await? else {
+                // Excluir de novo uma tarefa já excluída não é conflito
+                if is_deleted(conn, &id, owner_id).await? {
+                    let resolution = Resolution {
+                        deleted: true,
+                        ..Resolution::applied(
+                            StatusCode::OK,
+                            "A tarefa já havia sido excluída",
+                            None,
+                        )
+                    };
+                    return Ok((id, resolution));
+                }
+                return Ok((id.clone(), missing(conn, &id, owner_id).await?));
+            };
+            match check_base(&existing, base_version, base_updated_at) {
+                Ok(true) => {}
+                Ok(false) => return Ok((id, Resolution::conflict(existing))),
+                Err(message) => {
+                    return Ok((
+                        id,
+                        Resolution::rejected(StatusCode::UNPROCESSABLE_ENTITY, message),
+                    ))
+                }
+            }
+            let deleted = delete_task_tree(conn, owner_id, &id, owner_id, None).await?;
+            effects.deleted(deleted);
+            let resolution = Resolution {
+                deleted: true,
+                ..Resolution::applied(StatusCode::OK, "Tarefa excluída com sucesso", None)
+            };
+            Ok((id, resolution))
+        }
+    }
+}
+
+// Resolução para uma tarefa que não existe: excluída no servidor ou desconhecida
+async fn missing(
+    conn: &mut SqliteConnection,
+    id: &str,
+    owner_id: &str,
+) -> Result<Resolution, sqlx::Error> {
+    if is_deleted(conn, id, owner_id).await? {
+        Ok(Resolution::deleted(id))
+    } else {
+        Ok(Resolution::rejected(
+            StatusCode::NOT_FOUND,
+            format!("Tarefa com ID {} não encontrada", id),
+        ))
+    }
+}
+
+// Handler para baixar as tarefas alteradas e excluídas desde o cursor
+#[utoipa::path(
+    tag = "sincronização",
+    summary = "Baixa as tarefas alteradas e excluídas desde o cursor",
+    params(SyncQuery),
+    responses(
+        (status = 200, body = ApiResponse<SyncPage>),
+        (status = 400, description = "Cursor inválido", body = Message),
+    )
+)]
+#[get("/sync")]
+pub async fn pull_changes(
+    db: web::Data<SqlitePool>,
+    user: AuthUser,
+    query: web::Query<SyncQuery>,
+) -> Result<impl Responder, AppError> {
+    let since = parse_cursor(query.since.as_deref()).map_err(AppError::BadRequest)?;
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    let result: Result<SyncPage, sqlx::Error> = async {
+        // As duas leituras na mesma transação veem o mesmo estado do banco
+        let mut tx = db.begin().await?;
+        let mut changes = sqlx::query_as::<_, Change>(
+            "SELECT seq, task_id, deleted_at FROM task_changes WHERE owner_id = ? AND seq > ? ORDER BY seq LIMIT ?",
+        )
+        .bind(&user.id)
+        .bind(since)
+        .bind(limit + 1)
+        .fetch_all(&mut *tx)
+        .await?;
+        let has_more = changes.len() as i64 > limit;
+        changes.truncate(limit as usize);
+        let cursor = changes.last().map_or(since, |change| change.seq);
+
+        let tasks = sqlx::query_as::<_, Task>(&format!(
+            r#"
+            SELECT tasks.*, {}, {}
+            FROM task_changes c
+            JOIN tasks ON tasks.id = c.task_id AND tasks.owner_id = c.owner_id
+            WHERE c.owner_id = ? AND c.seq > ? AND c.seq <= ?
+            ORDER BY c.seq
+            "#,
+            PROGRESS, LABELS
+        ))
+        .bind(&user.id)
+        .bind(since)
+        .bind(cursor)
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        // Na primeira sincronização o cliente ainda não tem nada a remover
+        let tombstones = changes
+            .into_iter()
+            .filter(|_| since > 0)
+            .filter_map(|change| {
+                change.deleted_at.map(|deleted_at| Tombstone {
+                    id: change.task_id,
+                    deleted_at,
+                })
+            })
+            .collect();
+
+        Ok(SyncPage {
+            tasks,
+            tombstones,
+            cursor: cursor.to_string(),
+            has_more,
+        })
+    }
+    .await;
+
+    let page = result?;
+    Ok(HttpResponse::Ok().json(ApiResponse {
+        success: true,
+        message: format!(
+            "{} tarefa(s) alterada(s) e {} excluída(s)",
+            page.tasks.len(),
+            page.tombstones.len()
+        ),
+        data: Some(page),
+    }))
+}
+
+// Handler para enviar as alterações feitas offline. Cada alteração é aplicada ou
+// recusada individualmente; as que conflitam com o servidor voltam com a cópia atual
+// da tarefa para o cliente resolver. Depois do envio, o cliente baixa as alterações
+// com GET /sync a partir do cursor que já tinha.
+#[utoipa::path(
+    tag = "sincronização",
+    summary = "Envia as alterações feitas offline",
+    request_body = SyncPush,
+    responses(
+        (status = 200, description = "Resultado de cada alteração; success é false se houver conflitos ou recusas", body = ApiResponse<Vec<SyncResult>>),
+        (status = 422, description = "Alterações demais em um envio", body = Message),
+    )
+)]
+#[post("/sync")]
+pub async fn push_changes(
+    db: web::Data<SqlitePool>,
+    events: web::Data<TaskEvents>,
+    reminders: web::Data<Reminders>,
+    attachments: web::Data<Attachments>,
+    user: AuthUser,
+    push: web::Json<SyncPush>,
+) -> Result<impl Responder, AppError> {
+    let changes = push.into_inner().changes;
+    if changes.len() > MAX_PUSH_SIZE {
+        return Err(AppError::validation(format!(
+            "Envie no máximo {} alterações por vez",
+            MAX_PUSH_SIZE
+        )));
+    }
+
+    let result: Result<(Vec<SyncResult>, Effects), sqlx::Error> = async {
+        let mut tx = db.begin().await?;
+        // Obtém o bloqueio de escrita antes da primeira leitura, para que as versões
+        // comparadas não mudem até a gravação
+        sqlx::query("UPDATE tasks SET version = version WHERE 0")
+            .execute(&mut *tx)
+            .await?;
+
+        let mut results = Vec::with_capacity(changes.len());
+        let mut effects = Effects::default();
+        for (index, change) in changes.into_iter().enumerate() {
+            let op = change.name();
+            let mut savepoint = tx.begin().await?;
+            let mut change_effects = Effects::default();
+            let (id, resolution) =
+                apply_change(&mut savepoint, &user.id, change, &mut change_effects).await?;
+            if resolution.outcome == SyncOutcome::Applied {
+                savepoint.commit().await?;
+                effects.extend(change_effects);
+            } else {
+                savepoint.rollback().await?;
+            }
+            results.push(SyncResult {
+                index,
+                op,
+                id,
+                outcome: resolution.outcome,
+                status: resolution.status.as_u16(),
+                message: resolution.message,
+                task: resolution.task,
+                deleted: resolution.deleted,
+            });
+        }
+        tx.commit().await?;
+        Ok((results, effects))
+    }
+    .await;
+
+    let (results, effects) = result?;
+    effects
+        .dispatch(db.get_ref(), &events, &reminders, &attachments)
+        .await;
+
+    let count = |outcome| results.iter().filter(|r| r.outcome == outcome).count();
+    let (applied, conflicts, rejected) = (
+        count(SyncOutcome::Applied),
+        count(SyncOutcome::Conflict),
+        count(SyncOutcome::Rejected),
+    );
+    Ok(HttpResponse::Ok().json(ApiResponse {
+        success: conflicts + rejected == 0,
+        message: format!(
+            "{} alteração(ões) aplicada(s), {} em conflito e {} recusada(s)",
+            applied, conflicts, rejected
+        ),
+        data: Some(results),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+
+    fn task() -> Task {
+        serde_json::from_value(serde_json::json!({
+            "id": "3f1c2b9e-0000-4000-8000-000000000001",
+            "title": "Relatório",
+            "description": "",
+            "version": 3,
+            "updated_at": "2024-03-02T11:30:00.123Z",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn cursor_defaults_to_the_beginning() {
+        assert_eq!(parse_cursor(None), Ok(0));
+        assert_eq!(parse_cursor(Some("0")), Ok(0));
+        assert_eq!(parse_cursor(Some("15")), Ok(15));
+    }
+
+    #[test]
+    fn invalid_cursor_is_rejected() {
+        for cursor in ["", "-1", "abc", "1.5"] {
+            assert!(parse_cursor(Some(cursor)).is_err(), "{:?}", cursor);
+        }
+    }
+
+    #[test]
+    fn base_is_required() {
+        assert!(check_base(&task(), None, None).is_err());
+    }
+
+    #[test]
+    fn base_version_alone_is_compared() {
+        let task = task();
+        assert_eq!(check_base(&task, Some(3), None), Ok(true));
+        assert_eq!(check_base(&task, Some(2), None), Ok(false));
+    }
+
+    #[test]
+    fn base_updated_at_alone_is_compared() {
+        let task = task();
+        let earlier = task.updated_at - Duration::milliseconds(1);
+        assert_eq!(check_base(&task, None, Some(task.updated_at)), Ok(true));
+        assert_eq!(check_base(&task, None, Some(earlier)), Ok(false));
+    }
+
+    #[test]
+    fn both_bases_must_match() {
+        let task = task();
+        let earlier = task.updated_at - Duration::milliseconds(1);
+        assert_eq!(check_base(&task, Some(3), Some(task.updated_at)), Ok(true));
+        assert_eq!(check_base(&task, Some(3), Some(earlier)), Ok(false));
+        assert_eq!(check_base(&task, Some(2), Some(task.updated_at)), Ok(false));
+    }
+}
@@ -0,0 +1,94 @@
+// Gera frontend/src/types/generated.ts a partir dos modelos em Rust. O teste falha quando
+// o arquivo versionado está desatualizado; para regravá-lo:
+//
+//     UPDATE_TS_TYPES=1 cargo test typescript
+use std::env;
+use std::fs;
+use std::path::Path;
+use ts_rs::TS;
+
+use crate::error::FieldError;
+use crate::labels::TaskLabel;
+use crate::priority::TaskPriority;
+use crate::status::TaskStatus;
+use crate::{ApiResponse, Task, TaskUpdate};
+
+const OUTPUT: &str = "frontend/src/types/generated.ts";
+
+const HEADER: &str = "\
+// Gerado a partir dos modelos em Rust (src/typescript.rs). Não edite à mão; para atualizar:
+//
+//     UPDATE_TS_TYPES=1 cargo test typescript
+";
+
+// Quebra a declaração gerada em uma linha por membro: `type A = { a: string, b: number, }`
+// vira um bloco com `a: string;` e `b: number;`, e os membros de enums terminam com vírgula.
+fn format_decl(decl: &str) -> String {
+    let (Some(open), Some(close)) = (decl.find('{'), decl.rfind('}')) else {
+        return format!("export {};\n", decl);
+    };
+    let is_enum = decl.starts_with("enum ");
+    let separator = if is_enum { "," } else { ";" };
+
+    // Separa os membros pelas vírgulas fora de <>, {}, () e []
+    let mut members = Vec::new();
+    let mut depth = 0;
+    let mut current = String::new();
+    for c in decl[open + 1..close].chars() {
+        match c {
+            '<' | '{' | '(' | '[' => depth += 1,
+            '>' | '}' | ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                members.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    members.push(current.trim().to_string());
+
+    let mut out = format!("export {}{{\n", &decl[..open]);
+    for member in members.iter().filter(|member| !member.is_empty()) {
+        out.push_str(&format!("    {}{}\n", member, separator));
+    }
+    out.push('}');
+    if !is_enum {
+        out.push(';');
+    }
+    out.push('\n');
+    out
+}
+
+fn render() -> String {
+    let decls = [
+        TaskStatus::decl(),
+        TaskPriority::decl(),
+        TaskLabel::decl(),
+        Task::decl(),
+        TaskUpdate::decl(),
+        ApiResponse::<()>::decl(),
+        FieldError::decl(),
+    ];
+    let body: Vec<String> = decls.iter().map(|decl| format_decl(decl)).collect();
+    format!("{}\n{}", HEADER, body.join("\n"))
+}
+
+#[test]
+fn typescript_types_are_up_to_date() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(OUTPUT);
+    let expected = render();
+
+    if env::var_os("UPDATE_TS_TYPES").is_some() {
+        fs::write(&path, expected).expect("Falha ao gravar os tipos TypeScript");
+        return;
+    }
+
+    let current = fs::read_to_string(&path).unwrap_or_default();
+    assert!(
+        current == expected,
+        "{} está desatualizado em relação aos modelos em Rust; rode `UPDATE_TS_TYPES=1 cargo test typescript`",
+        OUTPUT
+    );
+}
@@ -0,0 +1,105 @@
+use actix_web::{dev::Payload, web, FromRequest, HttpRequest};
+use futures_util::future::LocalBoxFuture;
+use serde::de::DeserializeOwned;
+use validator::{Validate, ValidationError, ValidationErrors};
+
+use crate::error::{AppError, FieldError};
+use crate::priority::TaskPriority;
+
+// Corpo JSON desserializado e validado pelas regras de #[validate(...)] do modelo.
+// Diferente de web::Json, os erros apontam os campos: um valor do tipo errado (como um
+// status desconhecido) ou recusado pelas regras vira um 422 com a lista em `errors`.
+// O limite de tamanho e o JSON malformado seguem o JsonConfig da aplicação.
+pub struct ValidJson<T>(pub T);
+
+impl<T> ValidJson<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: DeserializeOwned + Validate + 'static> FromRequest for ValidJson<T> {
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let json = web::Json::<serde_json::Value>::from_request(req, payload);
+        Box::pin(async move {
+            let value = json.await?.into_inner();
+            let data: T = serde_path_to_error::deserialize(value).map_err(invalid_value)?;
+            data.validate().map_err(AppError::from)?;
+            Ok(ValidJson(data))
+        })
+    }
+}
+
+// Valor que não pôde ser lido no tipo do campo. Um campo obrigatório ausente é apontado
+// na raiz do objeto; o nome vem da mensagem do serde ("missing field `title`").
+fn invalid_value(e: serde_path_to_error::Error<serde_json::Error>) -> AppError {
+    let detail = e.inner().to_string();
+    let missing = detail
+        .strip_prefix("missing field `")
+        .and_then(|rest| rest.split('`').next());
+    let (field, message) = match missing {
+        Some(name) => (name.to_string(), "O campo é obrigatório".to_string()),
+        None if e.path().to_string() == "." => {
+            return AppError::validation(format!("Dados inválidos: {}", detail))
+        }
+        None => (e.path().to_string(), format!("Valor inválido: {}", detail)),
+    };
+    AppError::Validation(
+        format!("Dados inválidos: {}: {}", field, message),
+        vec![FieldError { field, message }],
+    )
+}
+
+// Campos recusados, em ordem alfabética para que a resposta seja estável
+pub fn field_errors(errors: &ValidationErrors) -> Vec<FieldError> {
+    let mut fields: Vec<FieldError> = errors
+        .field_errors()
+        .into_iter()
+        .flat_map(|(field, errors)| {
+            errors.iter().map(move |error| FieldError {
+                field: field.to_string(),
+                message: error
+                    .message
+                    .as_ref()
+                    .map_or_else(|| error.code.to_string(), |message| message.to_string()),
+            })
+        })
+        .collect();
+    fields.sort_by(|a, b| a.field.cmp(&b.field));
+    fields
+}
+
+// Resumo dos campos recusados em uma linha, para respostas sem a lista de campos
+// (como o resultado de cada operação de um lote)
+pub fn summary(errors: &ValidationErrors) -> String {
+    let fields: Vec<String> = field_errors(errors)
+        .into_iter()
+        .map(|error| format!("{}: {}", error.field, error.message))
+        .collect();
+    format!("Dados inválidos: {}", fields.join("; "))
+}
+
+impl From<ValidationErrors> for AppError {
+    fn from(errors: ValidationErrors) -> Self {
+        AppError::Validation(summary(&errors), field_errors(&errors))
+    }
+}
+
+// Prioridade conhecida, de Baixa (1) a Urgente (4)
+pub fn priority(value: i32) -> Result<(), ValidationError> {
+    match TaskPriority::from_value(value) {
+        Some(_) => Ok(()),
+        None => Err(ValidationError::new("range")),
+    }
+}
+
+// Texto obrigatório: recusa valores vazios ou só com espaços
+pub fn not_blank(value: &str) -> Result<(), ValidationError> {
+    if value.trim().is_empty() {
+        return Err(ValidationError::new("blank").with_message("O campo é obrigatório".into()));
+    }
+    Ok(())
+}